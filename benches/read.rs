@@ -0,0 +1,102 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use num_complex::Complex32;
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use seify::Device;
+use seify::RxStreamer;
+use seify::SampleFormat;
+
+/// Global allocator counting the number of allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const READS: usize = 1000;
+
+fn read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read");
+    // CS16 capture for the file driver, converted to CF32 on every read
+    let path = std::env::temp_dir().join(format!("seify-bench-{}.iq", std::process::id()));
+    std::fs::write(&path, vec![0u8; 1 << 20]).unwrap();
+    let file = format!(
+        "driver=file, path={}, format=cs16, loop=true, sample_rate=1e12",
+        path.to_str().unwrap()
+    );
+
+    for (driver, args) in [
+        ("null", "driver=null".to_string()),
+        ("mock", "driver=mock".to_string()),
+        ("file", file),
+    ] {
+        let dev = Device::from_args(args).unwrap();
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        let mtu = rx.mtu().unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); mtu];
+        rx.activate().unwrap();
+
+        // allocations per read, after the streamer is set up
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for _ in 0..READS {
+            rx.read(&mut [&mut buf], 1000000).unwrap();
+        }
+        let allocs = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "{driver}: {:.2} allocations per read",
+            allocs as f64 / READS as f64
+        );
+
+        group.throughput(Throughput::Elements(mtu as u64));
+        group.bench_function(BenchmarkId::new("mtu", driver), |b| {
+            b.iter(|| rx.read(&mut [&mut buf], 1000000).unwrap())
+        });
+        // per-call overhead
+        group.throughput(Throughput::Elements(1));
+        group.bench_function(BenchmarkId::new("single", driver), |b| {
+            b.iter(|| rx.read(&mut [&mut buf[..1]], 1000000).unwrap())
+        });
+        rx.deactivate().unwrap();
+    }
+    group.finish();
+    std::fs::remove_file(path).unwrap();
+}
+
+fn convert(c: &mut Criterion) {
+    let n = 65536;
+    let src = vec![0u8; n * SampleFormat::CS16.bytes_per_sample()];
+    let mut dst = vec![Complex32::new(0.0, 0.0); n];
+
+    let mut group = c.benchmark_group("convert");
+    group.throughput(Throughput::Elements(n as u64));
+    group.bench_function("cf32_from_cs16", |b| {
+        b.iter(|| seify::convert::from_bytes(&src, &mut dst, SampleFormat::CS16))
+    });
+    let mut raw = src.clone();
+    group.bench_function("cs16_from_cf32", |b| {
+        b.iter(|| seify::convert::to_bytes(&dst, &mut raw, SampleFormat::CS16))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read, convert);
+criterion_main!(benches);
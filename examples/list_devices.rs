@@ -0,0 +1,80 @@
+use clap::Parser;
+use seify::Device;
+use seify::Range;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+}
+
+fn field(args: &seify::Args, key: &str) -> String {
+    args.get::<String>(key).unwrap_or_else(|_| "-".to_string())
+}
+
+fn range(r: &Option<Range>) -> String {
+    match r.as_ref().and_then(|r| Some((r.min()?, r.max()?))) {
+        Some((min, max)) => format!("{min:.3e}..{max:.3e}"),
+        None => "-".to_string(),
+    }
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let devs = seify::probe(&cli.args.parse()?)?;
+    if devs.is_empty() {
+        println!("No devices found.");
+        println!("Check that the radio is plugged in, that you have permissions to access it, and");
+        println!(
+            "that its driver is enabled: {:?}",
+            seify::available_drivers()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{:<14} {:<18} {:<12} args",
+        "driver", "serial", "bus/address"
+    );
+    for d in &devs {
+        let bus = match (d.get::<String>("bus"), d.get::<String>("address")) {
+            (Ok(b), Ok(a)) => format!("{b}/{a}"),
+            _ => "-".to_string(),
+        };
+        println!(
+            "{:<14} {:<18} {:<12} {d}",
+            field(d, "driver"),
+            field(d, "serial"),
+            bus
+        );
+    }
+
+    for d in devs {
+        println!();
+        println!("{d}");
+        // Open the device only for the snapshot of its capabilities.
+        let caps = match Device::from_args(&d).and_then(|dev| dev.capabilities()) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("  could not open: {e}");
+                continue;
+            }
+        };
+        for (name, channels) in [("rx", &caps.rx), ("tx", &caps.tx)] {
+            for (i, c) in channels.iter().enumerate() {
+                println!(
+                    "  {name}{i}: frequency {}, sample rate {}, gain {}",
+                    range(&c.frequency_range),
+                    range(&c.sample_rate_range),
+                    range(&c.gain_range)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
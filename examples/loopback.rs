@@ -0,0 +1,86 @@
+use clap::Parser;
+use num_complex::Complex32;
+
+use seify::Device;
+use seify::Direction::Rx;
+use seify::Direction::Tx;
+use seify::RxStreamer;
+use seify::TxStreamer;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+    /// Loopback Mode
+    #[clap(short, long, default_value = "BB")]
+    mode: String,
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let dev = Device::from_args(cli.args)?;
+    match dev.loopback_modes() {
+        Ok(modes) => println!("loopback modes: {modes:?}"),
+        Err(seify::Error::NotSupported) => {
+            println!("loopback not supported by {:?}", dev.driver());
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    }
+    println!("full duplex: {:?}", dev.full_duplex(Rx, 0)?);
+    dev.set_loopback(&cli.mode)?;
+
+    let rate = 1e6;
+    for d in [Rx, Tx] {
+        dev.set_frequency(d, 0, 915e6)?;
+        dev.set_sample_rate(d, 0, rate)?;
+    }
+
+    // linear chirp sweeping the band
+    let len = 1024;
+    let chirp: Vec<Complex32> = (0..len)
+        .map(|i| {
+            let t = i as f32 / len as f32;
+            Complex32::from_polar(0.5, std::f32::consts::PI * len as f32 * (t * t - t))
+        })
+        .collect();
+
+    let mut rx = dev.rx_streamer(&[0])?;
+    let mut tx = dev.tx_streamer(&[0])?;
+    rx.activate()?;
+    tx.activate()?;
+
+    tx.write_all(&[&chirp], None, true, 1000000)?;
+    let mut buf = vec![Complex32::new(0.0, 0.0); 8 * len];
+    rx.read_exact(&mut [&mut buf], 1000000)?;
+
+    tx.deactivate()?;
+    rx.deactivate()?;
+    dev.set_loopback("NONE")?;
+
+    // cross-correlate with the transmitted chirp
+    let energy = chirp.iter().map(|s| s.norm_sqr()).sum::<f32>();
+    let (delay, corr) = (0..buf.len() - len)
+        .map(|lag| {
+            let c = buf[lag..lag + len]
+                .iter()
+                .zip(chirp.iter())
+                .map(|(r, t)| r * t.conj())
+                .sum::<Complex32>()
+                .norm();
+            (lag, c)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    let power = buf.iter().map(|s| s.norm_sqr()).sum::<f32>() / buf.len() as f32;
+    println!(
+        "received power {power:.6}, correlation {:.6}, delay {delay} samples",
+        corr / energy
+    );
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use clap::Parser;
+use num_complex::Complex32;
+use serde_json::json;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use seify::Device;
+use seify::Direction::Rx;
+use seify::RxStreamer;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+    /// Center Frequency
+    #[clap(short, long, default_value_t = 100e6)]
+    frequency: f64,
+    /// Sample Rate
+    #[clap(short, long, default_value_t = 2.4e6)]
+    rate: f64,
+    /// Number of Samples to record (until Ctrl-C if not set)
+    #[clap(short, long)]
+    samples: Option<usize>,
+    /// Output File, without extension
+    #[clap(short, long, default_value = "recording")]
+    output: String,
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let dev = Device::from_args(cli.args)?;
+    dev.enable_agc(Rx, 0, true)?;
+    dev.set_frequency(Rx, 0, cli.frequency)?;
+    dev.set_sample_rate(Rx, 0, cli.rate)?;
+
+    let info = dev.info()?;
+    let hw = [Some(dev.driver().to_string()), info.get("serial").ok()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(" ");
+    let meta = json!({
+        "global": {
+            "core:datatype": if cfg!(target_endian = "little") { "cf32_le" } else { "cf32_be" },
+            "core:sample_rate": dev.sample_rate(Rx, 0)?,
+            "core:version": "1.0.0",
+            "core:hw": hw,
+            "core:recorder": "seify",
+            "seify:info": info,
+            "seify:capabilities": dev.capabilities().ok(),
+        },
+        "captures": [{
+            "core:sample_start": 0,
+            "core:frequency": dev.frequency(Rx, 0)?,
+            "core:datetime": now_rfc3339(),
+        }],
+        "annotations": [],
+    });
+    std::fs::write(
+        format!("{}.sigmf-meta", cli.output),
+        serde_json::to_string_pretty(&meta)?,
+    )?;
+
+    let terminate = Arc::new(AtomicBool::new(false));
+    ctrlc::set_handler({
+        let terminate = terminate.clone();
+        move || terminate.store(true, Ordering::Relaxed)
+    })?;
+
+    let mut file = BufWriter::new(File::create(format!("{}.sigmf-data", cli.output))?);
+    let mut rx = dev.rx_streamer(&[0])?;
+    let mut buf = vec![Complex32::new(0.0, 0.0); rx.mtu()?];
+    let mut written = 0;
+    rx.activate()?;
+    while !terminate.load(Ordering::Relaxed) && cli.samples.is_none_or(|s| written < s) {
+        let n = cli
+            .samples
+            .map_or(buf.len(), |s| buf.len().min(s - written));
+        let n = rx.read(&mut [&mut buf[..n]], 1000000)?;
+        for s in &buf[..n] {
+            file.write_all(&s.re.to_ne_bytes())?;
+            file.write_all(&s.im.to_ne_bytes())?;
+        }
+        written += n;
+    }
+    rx.deactivate()?;
+    file.flush()?;
+
+    println!("recorded {written} samples to {}.sigmf-data", cli.output);
+    Ok(())
+}
+
+/// Current time as an RFC 3339 timestamp (UTC).
+fn now_rfc3339() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let (days, rem) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    // civil from days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!(
+        "{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use num_complex::Complex32;
+
+use seify::AsyncRxAdapter;
+use seify::AsyncRxStreamer;
+use seify::Device;
+use seify::Direction::Rx;
+use seify::RxStreamer;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+}
+
+#[tokio::main]
+pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let dev = Device::from_args(cli.args)?;
+
+    dev.enable_agc(Rx, 0, true)?;
+    dev.set_frequency(Rx, 0, 927e6)?;
+    dev.set_sample_rate(Rx, 0, 3.2e6)?;
+
+    let mut rx = dev.rx_streamer(&[0])?;
+    rx.activate()?;
+    let mut rx = AsyncRxAdapter::new(rx);
+
+    let mut samps = [Complex32::new(0.0, 0.0); 8192];
+    for _ in 0..100 {
+        let n = rx.read(&mut [&mut samps], 200000).await?;
+        let power = samps[..n].iter().map(|s| s.norm_sqr()).sum::<f32>() / n.max(1) as f32;
+        println!("read {n} samples, mean power {power:.6}");
+    }
+
+    Ok(())
+}
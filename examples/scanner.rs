@@ -0,0 +1,88 @@
+use clap::Parser;
+use num_complex::Complex32;
+
+use seify::Device;
+use seify::Direction::Rx;
+use seify::RxStreamer;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+    /// Start Frequency
+    #[clap(long, default_value_t = 900e6)]
+    start: f64,
+    /// Stop Frequency
+    #[clap(long, default_value_t = 950e6)]
+    stop: f64,
+    /// Frequency Step
+    #[clap(long, default_value_t = 2e6)]
+    step: f64,
+    /// Sample Rate
+    #[clap(short, long, default_value_t = 2.4e6)]
+    rate: f64,
+    /// FFT Size
+    #[clap(short, long, default_value_t = 1024)]
+    fft_size: usize,
+    /// Number of strongest bins to print per step
+    #[clap(short, long, default_value_t = 3)]
+    peaks: usize,
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let dev = Device::from_args(cli.args)?;
+    let range = dev.frequency_range(Rx, 0)?;
+    for f in [cli.start, cli.stop] {
+        if !range.contains(f) {
+            return Err(format!("frequency {f} not in range {range:?}").into());
+        }
+    }
+
+    dev.enable_agc(Rx, 0, true)?;
+    dev.set_sample_rate(Rx, 0, cli.rate)?;
+    println!("settling time: {:?}", dev.tune_settling_time(Rx, 0).ok());
+
+    let mut planner = rustfft::FftPlanner::new();
+    let fft = planner.plan_fft_forward(cli.fft_size);
+    let mut samps = vec![Complex32::new(0.0, 0.0); cli.fft_size];
+
+    let mut rx = dev.rx_streamer(&[0])?;
+    rx.activate()?;
+
+    let mut freq = cli.start;
+    while freq <= cli.stop {
+        dev.set_frequency_settled(Rx, 0, freq)?;
+        // drop samples that were buffered before retuning
+        rx.read_exact(&mut [&mut samps], 1000000)?;
+        rx.read_exact(&mut [&mut samps], 1000000)?;
+        fft.process(&mut samps);
+
+        let n = samps.len();
+        let mut bins: Vec<(f64, f32)> = samps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                // fft shift to get the frequency offset of the bin
+                let offset = ((i + n / 2) % n) as f64 / n as f64 - 0.5;
+                (freq + offset * cli.rate, 10.0 * s.norm_sqr().log10())
+            })
+            .collect();
+        bins.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        print!("{:>12.0} Hz:", freq);
+        for (f, p) in bins.iter().take(cli.peaks) {
+            print!("  {f:.0} Hz ({p:.1} dB)");
+        }
+        println!();
+
+        freq += cli.step;
+    }
+
+    rx.deactivate()?;
+    Ok(())
+}
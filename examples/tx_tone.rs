@@ -0,0 +1,69 @@
+use clap::Parser;
+use num_complex::Complex32;
+
+use seify::Device;
+use seify::Direction::Tx;
+use seify::TxStreamer;
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// Device Filters
+    #[clap(short, long, default_value = "")]
+    args: String,
+    /// Center Frequency
+    #[clap(short, long, default_value_t = 2.45e9)]
+    frequency: f64,
+    /// Sample Rate
+    #[clap(short, long, default_value_t = 1e6)]
+    rate: f64,
+    /// Tone Offset from the Center Frequency
+    #[clap(short, long, default_value_t = 100e3)]
+    offset: f64,
+    /// Gain
+    #[clap(short, long)]
+    gain: Option<f64>,
+    /// Duration in Seconds
+    #[clap(short, long, default_value_t = 5.0)]
+    duration: f64,
+}
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Args::parse();
+
+    let dev = Device::from_args(cli.args)?;
+    if dev.num_channels(Tx).unwrap_or(0) == 0 {
+        println!("device has no TX channels");
+        return Ok(());
+    }
+
+    dev.set_frequency(Tx, 0, cli.frequency)?;
+    dev.set_sample_rate(Tx, 0, cli.rate)?;
+    if let Some(g) = cli.gain {
+        dev.set_gain(Tx, 0, g)?;
+    }
+
+    let mut tx = dev.tx_streamer(&[0])?;
+    let mtu = tx.mtu()?;
+    let total = (cli.duration * cli.rate) as usize;
+    let step = 2.0 * std::f64::consts::PI * cli.offset / cli.rate;
+    let mut phase = 0.0f64;
+    let mut buf = vec![Complex32::new(0.0, 0.0); mtu];
+
+    tx.activate()?;
+    let mut sent = 0;
+    while sent < total {
+        let n = std::cmp::min(mtu, total - sent);
+        for s in buf[..n].iter_mut() {
+            *s = Complex32::from_polar(0.7, phase as f32);
+            phase = (phase + step) % (2.0 * std::f64::consts::PI);
+        }
+        sent += n;
+        tx.write_all(&[&buf[..n]], None, sent == total, 1000000)?;
+    }
+    tx.deactivate()?;
+
+    println!("transmitted {sent} samples");
+    Ok(())
+}
@@ -13,12 +13,18 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_with::serde_as;
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::str::FromStr;
 
 use crate::Error;
 
 /// Arbitrary arguments and parameters.
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Equality and hashing use a canonical representation with lowercased keys, so that [`Args`]
+/// compare equal independent of their insertion order and the case of their keys. Values are
+/// compared as they are, since serials and paths are case-sensitive.
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 #[serde_as]
 pub struct Args {
@@ -74,6 +80,17 @@ impl Args {
             self.set(k, v);
         }
     }
+    /// Canonical representation of the [`Args`], i.e., the key-value pairs with lowercased keys,
+    /// sorted by key.
+    pub fn canonical(&self) -> Vec<(String, String)> {
+        let mut v: Vec<(String, String)> = self
+            .map
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.clone()))
+            .collect();
+        v.sort();
+        v
+    }
     /// Try to [`Deserialize`] a value of type `D` from the JSON-serialized [`Args`].
     pub fn deserialize<D: for<'a> Deserialize<'a>>(&self) -> Option<D> {
         let s = serde_json::to_string(&self).ok()?;
@@ -81,6 +98,20 @@ impl Args {
     }
 }
 
+impl PartialEq for Args {
+    fn eq(&self, other: &Self) -> bool {
+        self.map.len() == other.map.len() && self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for Args {}
+
+impl Hash for Args {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
 impl std::fmt::Debug for Args {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.map.fmt(f)
@@ -188,6 +219,31 @@ impl Default for Args {
 mod tests {
     use super::*;
 
+    #[test]
+    fn eq_order() {
+        let a: Args = "foo=bar, fo=ba".parse().unwrap();
+        let b: Args = "fo=ba, foo=bar".parse().unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, "foo=bar".parse().unwrap());
+    }
+    #[test]
+    fn eq_case() {
+        let a: Args = "driver=rtlsdr, serial=0000000A".parse().unwrap();
+        let b: Args = "Serial=0000000A, DRIVER=rtlsdr".parse().unwrap();
+        assert_eq!(a, b);
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(a.clone());
+        assert!(set.contains(&b));
+
+        let c: Args = "driver=rtlsdr, serial=0000000a".parse().unwrap();
+        assert_ne!(a, c);
+        assert!(!set.contains(&c));
+        assert_ne!(
+            Args::from("path=/tmp/A.iq").unwrap(),
+            Args::from("path=/tmp/a.iq").unwrap()
+        );
+    }
     #[test]
     fn deserialize_empty() {
         let c: Args = "".parse().unwrap();
@@ -0,0 +1,77 @@
+use num_complex::Complex32;
+use std::future::Future;
+
+use crate::Error;
+use crate::RxStreamer;
+
+/// Receive samples asynchronously from a [Device](crate::Device).
+///
+/// Use [`AsyncRxAdapter`] to turn any blocking [`RxStreamer`] into an `AsyncRxStreamer`.
+pub trait AsyncRxStreamer: Send {
+    /// Read samples from the stream into the provided buffers.
+    ///
+    /// Same semantics as [`RxStreamer::read`], but does not block the async runtime.
+    fn read(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> impl Future<Output = Result<usize, Error>> + Send;
+}
+
+/// Adapter, running the blocking reads of an [`RxStreamer`] on the tokio blocking thread pool.
+///
+/// Has to be used from within a tokio runtime.
+pub struct AsyncRxAdapter<S: RxStreamer + 'static> {
+    streamer: Option<S>,
+    buffers: Vec<Vec<Complex32>>,
+}
+
+impl<S: RxStreamer + 'static> AsyncRxAdapter<S> {
+    /// Wrap a blocking [`RxStreamer`].
+    pub fn new(streamer: S) -> Self {
+        Self {
+            streamer: Some(streamer),
+            buffers: Vec::new(),
+        }
+    }
+
+    /// Get back the wrapped streamer.
+    ///
+    /// Returns `None` if a read was cancelled while it was running on the thread pool.
+    pub fn into_inner(self) -> Option<S> {
+        self.streamer
+    }
+}
+
+impl<S: RxStreamer + 'static> AsyncRxStreamer for AsyncRxAdapter<S> {
+    async fn read(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        // a cancelled read leaves the streamer on the blocking thread
+        let mut streamer = self.streamer.take().ok_or(Error::Inactive)?;
+        let mut bufs = std::mem::take(&mut self.buffers);
+        bufs.resize_with(buffers.len(), Vec::new);
+        for (b, o) in bufs.iter_mut().zip(buffers.iter()) {
+            b.resize(o.len(), Complex32::new(0.0, 0.0));
+        }
+
+        let (streamer, bufs, res) = tokio::task::spawn_blocking(move || {
+            let mut refs: Vec<&mut [Complex32]> = bufs.iter_mut().map(|b| &mut b[..]).collect();
+            let res = streamer.read(&mut refs, timeout_us);
+            (streamer, bufs, res)
+        })
+        .await
+        .map_err(|e| Error::Misc(e.to_string()))?;
+
+        if let Ok(n) = res {
+            for (o, b) in buffers.iter_mut().zip(bufs.iter()) {
+                o[..n].copy_from_slice(&b[..n]);
+            }
+        }
+        self.streamer = Some(streamer);
+        self.buffers = bufs;
+        res
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Args;
+use crate::Device;
+use crate::Driver;
+use crate::Error;
+use crate::GenericDevice;
+
+/// Selectors that none of the drivers implement.
+const UNSUPPORTED: [&str; 3] = ["bus", "address", "fd"];
+
+/// Builder to open a [`Device`] without assembling an args string by hand.
+///
+/// The selectors are translated into the [`Args`] that [`Device::from_args`] understands, e.g.,
+/// `serial` and `driver`.
+///
+/// ```no_run
+/// use seify::DeviceBuilder;
+/// use seify::Driver;
+///
+/// let dev = DeviceBuilder::new()
+///     .driver(Driver::RtlSdr)
+///     .serial("00000001")
+///     .arg("rx_sample_rate", "3.2e6")
+///     .open()?;
+/// # Ok::<(), seify::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DeviceBuilder {
+    args: Args,
+}
+
+impl DeviceBuilder {
+    /// Create a builder without any selectors, which opens the first device found.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only open devices of the given `driver`.
+    pub fn driver(mut self, driver: Driver) -> Self {
+        self.args.set("driver", driver.to_string());
+        self
+    }
+
+    /// Select the device by its serial number.
+    pub fn serial(mut self, serial: &str) -> Self {
+        self.args.set("serial", serial);
+        self
+    }
+
+    /// Select the device by its USB `bus` number and `address`.
+    ///
+    /// None of the drivers in this build can select a device by USB address yet, so
+    /// [`open`](Self::open) returns [`Error::NotSupported`].
+    pub fn bus_address(mut self, bus: u8, address: u8) -> Self {
+        self.args.set("bus", bus.to_string());
+        self.args.set("address", address.to_string());
+        self
+    }
+
+    /// Select the device by an already opened file descriptor, e.g., one that was handed over
+    /// by the Android USB host API.
+    ///
+    /// None of the drivers in this build can open a device from a file descriptor yet, so
+    /// [`open`](Self::open) returns [`Error::NotSupported`].
+    pub fn fd(mut self, fd: i32) -> Self {
+        self.args.set("fd", fd.to_string());
+        self
+    }
+
+    /// Set an arbitrary argument, like `rx_frequency` or a driver-specific option.
+    pub fn arg(mut self, key: &str, value: &str) -> Self {
+        self.args.set(key, value);
+        self
+    }
+
+    /// Args that the builder passes to [`Device::from_args`].
+    ///
+    /// Returns [`Error::ValueError`] if the selectors conflict, i.e., if both a `serial` and an
+    /// `fd` are set, and [`Error::NotSupported`] for selectors that no driver implements.
+    pub fn args(&self) -> Result<Args, Error> {
+        let set = |k: &str| self.args.get::<String>(k).is_ok();
+        if set("serial") && set("fd") {
+            log::warn!("device builder: serial and fd select the device in conflicting ways");
+            return Err(Error::ValueError);
+        }
+        if let Some(k) = UNSUPPORTED.iter().find(|k| set(k)) {
+            log::warn!("device builder: no driver supports selecting a device by {k}");
+            return Err(Error::NotSupported);
+        }
+        Ok(self.args.clone())
+    }
+
+    /// Open the device.
+    pub fn open(self) -> Result<Device<GenericDevice>, Error> {
+        Device::from_args(self.args()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args() {
+        let args = DeviceBuilder::new()
+            .driver(Driver::Mock)
+            .serial("00000001")
+            .arg("tone", "2e3")
+            .args()
+            .unwrap();
+        assert_eq!(args.get::<Driver>("driver").unwrap(), Driver::Mock);
+        assert_eq!(args.get::<String>("serial").unwrap(), "00000001");
+        assert_eq!(args.get::<f64>("tone").unwrap(), 2e3);
+
+        for b in [
+            DeviceBuilder::new().bus_address(1, 12),
+            DeviceBuilder::new().fd(3),
+        ] {
+            assert!(matches!(b.args(), Err(Error::NotSupported)));
+            assert!(matches!(b.open(), Err(Error::NotSupported)));
+        }
+
+        let b = DeviceBuilder::new().serial("00000001").fd(3);
+        assert!(matches!(b.args(), Err(Error::ValueError)));
+        assert!(matches!(b.open(), Err(Error::ValueError)));
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn open() {
+        let dev = DeviceBuilder::new()
+            .driver(Driver::Mock)
+            .arg("rx_frequency", "100e6")
+            .open()
+            .unwrap();
+        assert_eq!(dev.driver(), Driver::Mock);
+        assert_eq!(dev.frequency(crate::Direction::Rx, 0).unwrap(), 100e6);
+    }
+}
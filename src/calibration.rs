@@ -0,0 +1,169 @@
+use crate::Direction;
+use crate::Error;
+
+const MAGIC: &[u8; 4] = b"SCAL";
+const VERSION: u8 = 1;
+
+const FLAG_DC_OFFSET: u8 = 1 << 0;
+const FLAG_IQ_BALANCE: u8 = 1 << 1;
+
+/// Calibration values of one channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationEntry {
+    /// Direction of the channel.
+    pub direction: Direction,
+    /// Channel index.
+    pub channel: usize,
+    /// DC offset correction (I, Q).
+    pub dc_offset: Option<(f64, f64)>,
+    /// IQ balance correction (I, Q).
+    pub iq_balance: Option<(f64, f64)>,
+}
+
+/// Calibration results of a device, see
+/// [`DeviceTrait::export_calibration`](crate::DeviceTrait::export_calibration).
+///
+/// The binary format starts with the magic `SCAL` and a version byte, followed by the number of
+/// entries (`u32`). Each entry is made up of the direction (`u8`, `0` for RX), the channel
+/// (`u32`), a flags byte, indicating which corrections are present, and the corrections as pairs
+/// of `f64`. All values are little endian.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Calibration {
+    /// Calibration values per channel.
+    pub entries: Vec<CalibrationEntry>,
+}
+
+impl Calibration {
+    /// Serialize to the versioned binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(MAGIC);
+        v.push(VERSION);
+        v.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for e in &self.entries {
+            v.push(match e.direction {
+                Direction::Rx => 0,
+                Direction::Tx => 1,
+            });
+            v.extend_from_slice(&(e.channel as u32).to_le_bytes());
+            let mut flags = 0;
+            if e.dc_offset.is_some() {
+                flags |= FLAG_DC_OFFSET;
+            }
+            if e.iq_balance.is_some() {
+                flags |= FLAG_IQ_BALANCE;
+            }
+            v.push(flags);
+            for (a, b) in [e.dc_offset, e.iq_balance].into_iter().flatten() {
+                v.extend_from_slice(&a.to_le_bytes());
+                v.extend_from_slice(&b.to_le_bytes());
+            }
+        }
+        v
+    }
+
+    /// Parse the versioned binary format.
+    ///
+    /// Fails with [`Error::ValueError`] if the data is malformed or of an unknown version.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let mut r = Reader(data);
+        if r.take(4)? != MAGIC || r.u8()? != VERSION {
+            return Err(Error::ValueError);
+        }
+        let n = r.u32()?;
+        let mut entries = Vec::new();
+        for _ in 0..n {
+            let direction = match r.u8()? {
+                0 => Direction::Rx,
+                1 => Direction::Tx,
+                _ => return Err(Error::ValueError),
+            };
+            let channel = r.u32()? as usize;
+            let flags = r.u8()?;
+            let dc_offset = if flags & FLAG_DC_OFFSET != 0 {
+                Some((r.f64()?, r.f64()?))
+            } else {
+                None
+            };
+            let iq_balance = if flags & FLAG_IQ_BALANCE != 0 {
+                Some((r.f64()?, r.f64()?))
+            } else {
+                None
+            };
+            entries.push(CalibrationEntry {
+                direction,
+                channel,
+                dc_offset,
+                iq_balance,
+            });
+        }
+        if !r.0.is_empty() {
+            return Err(Error::ValueError);
+        }
+        Ok(Self { entries })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(Error::ValueError);
+        }
+        let (a, b) = self.0.split_at(n);
+        self.0 = b;
+        Ok(a)
+    }
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    fn f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let c = Calibration {
+            entries: vec![
+                CalibrationEntry {
+                    direction: Direction::Rx,
+                    channel: 0,
+                    dc_offset: Some((0.01, -0.02)),
+                    iq_balance: Some((1.0, 0.5)),
+                },
+                CalibrationEntry {
+                    direction: Direction::Tx,
+                    channel: 1,
+                    dc_offset: None,
+                    iq_balance: Some((0.9, -0.1)),
+                },
+            ],
+        };
+        let b = c.to_bytes();
+        assert_eq!(Calibration::from_bytes(&b).unwrap(), c);
+    }
+
+    #[test]
+    fn malformed() {
+        let mut b = Calibration::default().to_bytes();
+        assert!(Calibration::from_bytes(&b).unwrap().entries.is_empty());
+        b[4] = VERSION + 1;
+        assert!(matches!(
+            Calibration::from_bytes(&b),
+            Err(Error::ValueError)
+        ));
+        assert!(matches!(
+            Calibration::from_bytes(&b[..3]),
+            Err(Error::ValueError)
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::DeviceTrait;
+use crate::Direction;
+use crate::Driver;
+use crate::Error;
+use crate::Range;
+
+/// Snapshot of what a device can do, see [`DeviceTrait::capabilities`].
+///
+/// Values that the driver does not support are `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// SDR [driver](Driver)
+    pub driver: Driver,
+    /// Capabilities of the RX channels, one entry per channel.
+    pub rx: Vec<ChannelCapabilities>,
+    /// Capabilities of the TX channels, one entry per channel.
+    pub tx: Vec<ChannelCapabilities>,
+}
+
+/// Capabilities of a single channel, see [`Capabilities`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelCapabilities {
+    /// Available antenna ports.
+    pub antennas: Option<Vec<String>>,
+    /// AGC support.
+    pub supports_agc: Option<bool>,
+    /// Range of the overall gain.
+    pub gain_range: Option<Range>,
+    /// Range of the center frequency.
+    pub frequency_range: Option<Range>,
+    /// Range of the sample rate.
+    pub sample_rate_range: Option<Range>,
+    /// Range of the baseband filter bandwidth.
+    pub bandwidth_range: Option<Range>,
+}
+
+fn supported<T>(r: Result<T, Error>) -> Result<Option<T>, Error> {
+    match r {
+        Ok(v) => Ok(Some(v)),
+        Err(Error::NotSupported) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+impl Capabilities {
+    /// Query the capabilities of a device through the [`DeviceTrait`].
+    pub fn from_device<D: DeviceTrait + ?Sized>(dev: &D) -> Result<Self, Error> {
+        let channels = |direction: Direction| -> Result<Vec<ChannelCapabilities>, Error> {
+            let n = supported(dev.num_channels(direction))?.unwrap_or(0);
+            (0..n)
+                .map(|c| {
+                    Ok(ChannelCapabilities {
+                        antennas: supported(dev.antennas(direction, c))?,
+                        supports_agc: supported(dev.supports_agc(direction, c))?,
+                        gain_range: supported(dev.gain_range(direction, c))?,
+                        frequency_range: supported(dev.frequency_range(direction, c))?,
+                        sample_rate_range: supported(dev.get_sample_rate_range(direction, c))?,
+                        bandwidth_range: supported(dev.get_bandwidth_range(direction, c))?,
+                    })
+                })
+                .collect()
+        };
+        Ok(Self {
+            driver: dev.driver(),
+            rx: channels(Direction::Rx)?,
+            tx: channels(Direction::Tx)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RangeItem;
+
+    #[test]
+    fn serialize() {
+        let c = Capabilities {
+            driver: Driver::RtlSdr,
+            rx: vec![ChannelCapabilities {
+                antennas: Some(vec!["RX".to_string()]),
+                supports_agc: Some(true),
+                gain_range: Some(Range::new(vec![RangeItem::Interval(0.0, 50.0)])),
+                ..Default::default()
+            }],
+            tx: Vec::new(),
+        };
+        let s = serde_json::to_string(&c).unwrap();
+        let d: Capabilities = serde_json::from_str(&s).unwrap();
+        assert_eq!(d.driver, Driver::RtlSdr);
+        assert_eq!(d.rx.len(), 1);
+        assert!(d.rx[0].bandwidth_range.is_none());
+        assert_eq!(serde_json::to_string(&d).unwrap(), s);
+    }
+}
@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Args;
+use crate::Direction;
+use crate::Error;
+
+/// Set of channel parameters that are applied in one go, see
+/// [`DeviceTrait::apply_config`](crate::DeviceTrait::apply_config).
+///
+/// Only the fields that are set are applied.
+///
+/// ```
+/// use seify::DeviceConfig;
+///
+/// let config = DeviceConfig::new()
+///     .frequency(927e6)
+///     .sample_rate(3.2e6)
+///     .gain(30.0);
+/// assert_eq!(config.bandwidth, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceConfig {
+    /// Sample rate in samples per second.
+    pub sample_rate: Option<f64>,
+    /// Baseband filter bandwidth in Hz.
+    pub bandwidth: Option<f64>,
+    /// Center frequency in Hz.
+    pub frequency: Option<f64>,
+    /// Overall gain in dB.
+    pub gain: Option<f64>,
+}
+
+impl DeviceConfig {
+    /// Create an empty config.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the sample rate.
+    pub fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = Some(rate);
+        self
+    }
+    /// Set the baseband filter bandwidth.
+    pub fn bandwidth(mut self, bw: f64) -> Self {
+        self.bandwidth = Some(bw);
+        self
+    }
+    /// Set the center frequency.
+    pub fn frequency(mut self, frequency: f64) -> Self {
+        self.frequency = Some(frequency);
+        self
+    }
+    /// Set the overall gain.
+    pub fn gain(mut self, gain: f64) -> Self {
+        self.gain = Some(gain);
+        self
+    }
+    /// Parse the parameters of `direction` from direction-scoped `args`, e.g.,
+    /// `rx_sample_rate=3.2e6, rx_frequency=100e6, tx_gain=20`.
+    ///
+    /// The keys are `rx_` or `tx_` followed by the name of a field. Other keys are ignored, values
+    /// that are not numbers are an [`Error::ValueError`].
+    pub fn from_args(args: &Args, direction: Direction) -> Result<Self, Error> {
+        let get = |name: &str| match args.get::<f64>(&format!("{direction}_{name}")) {
+            Ok(v) => Ok(Some(v)),
+            Err(Error::NotFound) => Ok(None),
+            Err(_) => Err(Error::ValueError),
+        };
+        Ok(Self {
+            sample_rate: get("sample_rate")?,
+            bandwidth: get("bandwidth")?,
+            frequency: get("frequency")?,
+            gain: get("gain")?,
+        })
+    }
+}
+
+/// Gain setting of a chain, see [`DeviceTrait::set_gains`](crate::DeviceTrait::set_gains).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Gain {
+    /// Overall amplification in dB, distributed over the stages by the driver.
+    Overall(f64),
+    /// Amplification of the gain element `name` in dB, e.g., an LNA or VGA stage.
+    Stage {
+        /// Name of the gain element, as returned by
+        /// [`gain_elements`](crate::DeviceTrait::gain_elements).
+        name: String,
+        /// Gain in dB.
+        value: f64,
+    },
+}
+
+impl Gain {
+    /// Name of the setting, which is `gain` for the overall gain and the name of the stage
+    /// otherwise.
+    pub fn name(&self) -> &str {
+        match self {
+            Gain::Overall(_) => "gain",
+            Gain::Stage { name, .. } => name,
+        }
+    }
+}
+
+/// Wrap an error with the name of the parameter that could not be applied.
+pub(crate) fn failed(field: &str) -> impl FnOnce(Error) -> Error + '_ {
+    move |e| Error::Apply(field.to_string(), Box::new(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let c = DeviceConfig::new().sample_rate(1e6).gain(10.0);
+        assert_eq!(c.sample_rate, Some(1e6));
+        assert_eq!(c.gain, Some(10.0));
+        assert_eq!(c.bandwidth, None);
+        assert_eq!(c.frequency, None);
+    }
+
+    #[test]
+    fn from_args() {
+        let args: Args = "rx_sample_rate=3.2e6, tx_sample_rate=2e6, rx_gain=30, driver=mock"
+            .try_into()
+            .unwrap();
+        let rx = DeviceConfig::from_args(&args, Direction::Rx).unwrap();
+        assert_eq!(rx, DeviceConfig::new().sample_rate(3.2e6).gain(30.0));
+        let tx = DeviceConfig::from_args(&args, Direction::Tx).unwrap();
+        assert_eq!(tx, DeviceConfig::new().sample_rate(2e6));
+
+        let args: Args = "rx_gain=high".try_into().unwrap();
+        assert!(matches!(
+            DeviceConfig::from_args(&args, Direction::Rx),
+            Err(Error::ValueError)
+        ));
+    }
+
+    #[test]
+    fn failed_field() {
+        let e = failed("gain")(Error::NotSupported);
+        assert!(matches!(e, Error::Apply(ref f, _) if f == "gain"));
+        assert_eq!(e.to_string(), "Applying gain failed (Not Supported)");
+    }
+}
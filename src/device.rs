@@ -1,14 +1,23 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 use std::any::Any;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::config::failed;
 use crate::Args;
+use crate::Capabilities;
+use crate::DeviceConfig;
 use crate::Direction;
 use crate::Driver;
 use crate::Error;
+use crate::Gain;
 use crate::Range;
+use crate::RangeItem;
 use crate::RxStreamer;
+use crate::SampleFormat;
 use crate::TxStreamer;
 
 /// Central trait, implemented by hardware drivers.
@@ -28,18 +37,106 @@ pub trait DeviceTrait: Any + Send {
     /// Identifier for the device, e.g., its serial.
     fn id(&self) -> Result<String, Error>;
     /// Device info that can be displayed to the user.
+    ///
+    /// Drivers should populate the following keys, if available: `driver`, `serial`,
+    /// `firmware_version`, `fpga_version`, `fpga_loaded`, and `usb_speed`.
     fn info(&self) -> Result<Args, Error>;
+    /// Query the device info from the hardware again, updating the cached copy returned by
+    /// [`info`](Self::info).
+    ///
+    /// Drivers read static identity, like serials and firmware versions, once when the device is
+    /// opened, so that [`info`](Self::info) is cheap. The default returns [`info`](Self::info).
+    fn info_refresh(&self) -> Result<Args, Error> {
+        self.info()
+    }
     /// Number of supported Channels.
     fn num_channels(&self, direction: Direction) -> Result<usize, Error>;
     /// Full Duplex support.
     fn full_duplex(&self, direction: Direction, channel: usize) -> Result<bool, Error>;
 
+    /// Reset and re-initialize the device, e.g., to recover from a wedged USB connection.
+    ///
+    /// Open streamers become invalid and have to be recreated. Settings are restored to the state
+    /// after opening the device.
+    fn reset(&self) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Open a second, independent handle to the same device, e.g., to hand it to another thread.
+    ///
+    /// The default implementation re-opens the device with the [`info`](Self::info) of this one.
+    /// Settings are not copied. Drivers that claim the device exclusively, like USB devices, fail with
+    /// [`Error::Busy`] if the device is still open.
+    ///
+    /// The clone is a [`GenericDevice`] rather than a `Box<dyn DeviceTrait>`, since the trait
+    /// object needs its streamer types spelled out anyway and a `GenericDevice` is what
+    /// [`Device::from_args`] opens, so the clone can be wrapped with [`Device::from_impl`].
+    fn try_clone(&self) -> Result<GenericDevice, Error> {
+        Ok(Device::from_args(self.info()?)?.dev)
+    }
+
+    /// Set a driver-specific feature that is not covered by the generic API.
+    ///
+    /// This is an extension point for hardware specifics, e.g., enabling an expansion board with
+    /// `("expansion_board", "xb200")`. Unknown keys return [`Error::NotSupported`], invalid values
+    /// [`Error::ValueError`].
+    fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read back a driver-specific feature, see [`set_feature`](Self::set_feature).
+    fn feature(&self, key: &str) -> Result<String, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read the raw register at `addr` of the device.
+    ///
+    /// Only available with the `raw-registers` feature, as an escape hatch for experiments that are not
+    /// covered by the generic API. The address space is driver-specific.
+    #[cfg(feature = "raw-registers")]
+    fn read_register(&self, addr: u16) -> Result<u32, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Write `value` to the raw register at `addr` of the device.
+    ///
+    /// Only available with the `raw-registers` feature, see [`read_register`](Self::read_register).
+    ///
+    /// # Warning
+    ///
+    /// This bypasses all driver state and checks. Writing a register can put the hardware in a
+    /// state that is inconsistent with the settings cached by the driver, stop it from working
+    /// until it is power cycled, or damage attached equipment. Only use it if you know the register
+    /// map of the device.
+    #[cfg(feature = "raw-registers")]
+    fn write_register(&self, addr: u16, value: u32) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
     //================================ STREAMER ============================================
     /// Create an RX streamer.
+    ///
+    /// Fails with [`Error::InvalidChannel`] if `channels` contains an index not below
+    /// [`num_channels`](DeviceTrait::num_channels).
+    /// Fails with [`Error::Busy`] if the channels are already in use by another streamer.
     fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error>;
     /// Create a TX streamer.
+    ///
+    /// Fails with [`Error::InvalidChannel`] if `channels` contains an index not below
+    /// [`num_channels`](DeviceTrait::num_channels).
+    /// Fails with [`Error::Busy`] if the channels are already in use by another streamer.
     fn tx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::TxStreamer, Error>;
 
+    /// Enable or disable the RF chain of a channel, without tearing down its streamer.
+    fn set_channel_enabled(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
     //================================ ANTENNA ============================================
     /// List of available antenna ports.
     fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error>;
@@ -48,6 +145,23 @@ pub trait DeviceTrait: Any + Send {
     /// Set antenna port.
     fn set_antenna(&self, direction: Direction, channel: usize, name: &str) -> Result<(), Error>;
 
+    /// Enable or disable the bias tee, powering external components like LNAs through the antenna port.
+    ///
+    /// Drivers supporting it also accept a `bias_tee` argument when opening the device.
+    fn set_bias_tee(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Whether the bias tee is enabled.
+    fn bias_tee(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        Err(Error::NotSupported)
+    }
+
     //================================ AGC ============================================
     /// Does the device support automatic gain control?
     fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error>;
@@ -58,6 +172,74 @@ pub trait DeviceTrait: Any + Send {
     /// Returns true, if automatic gain control is enabled
     fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error>;
 
+    /// Set the target level of the automatic gain control in dBFS.
+    ///
+    /// Valid values are given by [`agc_setpoint_range`](Self::agc_setpoint_range).
+    fn set_agc_setpoint(
+        &self,
+        direction: Direction,
+        channel: usize,
+        dbfs: f64,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Target level of the automatic gain control in dBFS.
+    fn agc_setpoint(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Range of possible AGC target levels in dBFS.
+    fn agc_setpoint_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the attack time of the automatic gain control in seconds, i.e., how fast the gain is
+    /// reduced when the signal exceeds the setpoint.
+    ///
+    /// Valid values are given by [`agc_attack_range`](Self::agc_attack_range).
+    fn set_agc_attack(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Attack time of the automatic gain control in seconds.
+    fn agc_attack(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Range of possible AGC attack times in seconds.
+    fn agc_attack_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the decay time of the automatic gain control in seconds, i.e., how fast the gain is
+    /// increased when the signal falls below the setpoint.
+    ///
+    /// Valid values are given by [`agc_decay_range`](Self::agc_decay_range).
+    fn set_agc_decay(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Decay time of the automatic gain control in seconds.
+    fn agc_decay(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Range of possible AGC decay times in seconds.
+    fn agc_decay_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        Err(Error::NotSupported)
+    }
+
     //================================ GAIN ============================================
     /// List of available gain elements.
     ///
@@ -68,6 +250,10 @@ pub trait DeviceTrait: Any + Send {
     /// The gain will be distributed automatically across available elements.
     ///
     /// `gain`: the new amplification value in dB
+    ///
+    /// Drivers may reject gains outside of the [`gain_range`](DeviceTrait::gain_range) with
+    /// [`Error::OutOfRange`]. [`Device::set_gain`](crate::Device::set_gain) and
+    /// [`apply_config`](DeviceTrait::apply_config) clamp them instead.
     fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error>;
 
     /// Get the overall value of the gain elements in a chain in dB.
@@ -76,6 +262,82 @@ pub trait DeviceTrait: Any + Send {
     /// Get the overall [`Range`] of possible gain values.
     fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error>;
 
+    /// Smallest overall gain in dB, from the [`gain_range`](Self::gain_range).
+    fn min_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.gain_range(direction, channel)?
+            .min()
+            .ok_or(Error::NotSupported)
+    }
+
+    /// Largest overall gain in dB, from the [`gain_range`](Self::gain_range).
+    fn max_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.gain_range(direction, channel)?
+            .max()
+            .ok_or(Error::NotSupported)
+    }
+
+    /// Set the overall amplification in a chain on a normalized scale.
+    ///
+    /// `value` is mapped linearly from `0.0..=1.0` onto the [`gain_range`](DeviceTrait::gain_range),
+    /// snapping to the closest supported gain.
+    fn set_gain_normalized(
+        &self,
+        direction: Direction,
+        channel: usize,
+        value: f64,
+    ) -> Result<(), Error> {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(Error::OutOfRange(
+                Range::new(vec![RangeItem::Interval(0.0, 1.0)]),
+                value,
+            ));
+        }
+        let range = self.gain_range(direction, channel)?;
+        let (min, max) = range.min().zip(range.max()).ok_or(Error::NotSupported)?;
+        let gain = range
+            .closest(min + value * (max - min))
+            .ok_or(Error::NotSupported)?;
+        self.set_gain(direction, channel, gain)
+    }
+
+    /// Get the overall amplification in a chain on a normalized scale of `0.0..=1.0`.
+    ///
+    /// See [`set_gain_normalized`](DeviceTrait::set_gain_normalized).
+    fn gain_normalized(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        let range = self.gain_range(direction, channel)?;
+        let (min, max) = range.min().zip(range.max()).ok_or(Error::NotSupported)?;
+        Ok(self.gain(direction, channel)?.map(|g| {
+            if max > min {
+                ((g - min) / (max - min)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        }))
+    }
+
+    /// Distribute an overall gain across the individual gain elements.
+    ///
+    /// Elements are filled in the order of [`gain_elements`](DeviceTrait::gain_elements), i.e., stages
+    /// close to the antenna get their gain first. Returns the total gain that was achieved, which might
+    /// differ from `total_db`, if it is outside the combined range or not a possible step.
+    fn set_gain_distributed(
+        &self,
+        direction: Direction,
+        channel: usize,
+        total_db: f64,
+    ) -> Result<f64, Error> {
+        let elements = self.gain_elements(direction, channel)?;
+        let ranges = elements
+            .iter()
+            .map(|e| self.gain_element_range(direction, channel, e))
+            .collect::<Result<Vec<_>, _>>()?;
+        let gains = distribute_gain(&ranges, total_db).ok_or(Error::NotSupported)?;
+        for (e, g) in elements.iter().zip(gains.iter()) {
+            self.set_gain_element(direction, channel, e, *g)?;
+        }
+        Ok(gains.iter().sum())
+    }
+
     /// Set the value of a amplification element in a chain.
     ///
     /// ## Arguments
@@ -105,6 +367,33 @@ pub trait DeviceTrait: Any + Send {
         name: &str,
     ) -> Result<Range, Error>;
 
+    /// Apply a batch of overall and per-stage gains in order.
+    ///
+    /// All values are checked against their ranges before any gain is set, so that an invalid batch
+    /// does not leave the chain partially configured. If the device rejects a gain nevertheless,
+    /// [`Error::Apply`] reports the name of the failed gain, and the gains before it stay applied.
+    fn set_gains(&self, direction: Direction, channel: usize, gains: &[Gain]) -> Result<(), Error> {
+        for g in gains {
+            let (range, value) = match g {
+                Gain::Overall(v) => (self.gain_range(direction, channel), *v),
+                Gain::Stage { name, value } => {
+                    (self.gain_element_range(direction, channel, name), *value)
+                }
+            };
+            check_gain(range, value, false).map_err(failed(g.name()))?;
+        }
+        for g in gains {
+            match g {
+                Gain::Overall(v) => self.set_gain(direction, channel, *v),
+                Gain::Stage { name, value } => {
+                    self.set_gain_element(direction, channel, name, *value)
+                }
+            }
+            .map_err(failed(g.name()))?;
+        }
+        Ok(())
+    }
+
     //================================ FREQUENCY ============================================
 
     /// Get the ranges of overall frequency values.
@@ -149,9 +438,46 @@ pub trait DeviceTrait: Any + Send {
         args: Args,
     ) -> Result<(), Error>;
 
+    /// Tune to `frequency`, placing the first ("RF") component `offset` Hz below it.
+    ///
+    /// The last (baseband) component compensates for the `offset`, so that the overall frequency
+    /// matches. This moves the DC spur of the LO away from the signal of interest. Components in
+    /// between are set to zero. Devices with a single component only support an `offset` of zero.
+    fn tune(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        offset: f64,
+    ) -> Result<(), Error> {
+        let components = self.frequency_components(direction, channel)?;
+        match components.as_slice() {
+            [] => Err(Error::NotSupported),
+            [c] if offset == 0.0 => self.set_component_frequency(direction, channel, c, frequency),
+            [_] => Err(Error::NotSupported),
+            [rf, mid @ .., bb] => {
+                self.set_component_frequency(direction, channel, rf, frequency - offset)?;
+                for c in mid {
+                    self.set_component_frequency(direction, channel, c, 0.0)?;
+                }
+                self.set_component_frequency(direction, channel, bb, offset)
+            }
+        }
+    }
+
+    /// Time it takes after a frequency change until received or transmitted samples are valid.
+    ///
+    /// This is a conservative hint for applications that hop quickly over frequencies, e.g.,
+    /// scanners. See [`Device::set_frequency_settled`] for a blocking variant of
+    /// [`set_frequency`](Self::set_frequency).
+    fn tune_settling_time(&self, direction: Direction, channel: usize) -> Result<Duration, Error> {
+        Err(Error::NotSupported)
+    }
+
     /// List available tunable elements in the chain.
     ///
-    /// Elements should be in order RF to baseband.
+    /// Elements should be in order RF to baseband. The overall frequency is the sum of the
+    /// frequencies of all components.
     fn frequency_components(
         &self,
         direction: Direction,
@@ -197,6 +523,238 @@ pub trait DeviceTrait: Any + Send {
 
     /// Get the range of possible baseband sample rates.
     fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error>;
+
+    /// Get the baseband filter bandwidth of the chain in Hz.
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the baseband filter bandwidth of the chain in Hz.
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Get the range of possible baseband filter bandwidths.
+    fn get_bandwidth_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the baseband filter bandwidth to `fraction` of the current sample rate, clamped to the
+    /// [bandwidth range](Self::get_bandwidth_range).
+    ///
+    /// The fraction defaults to 0.75. Call this again after changing the sample rate.
+    fn set_bandwidth_auto(
+        &self,
+        direction: Direction,
+        channel: usize,
+        fraction: Option<f64>,
+    ) -> Result<(), Error> {
+        let fraction = fraction.unwrap_or(BANDWIDTH_FRACTION);
+        if fraction.is_nan() || fraction <= 0.0 {
+            return Err(Error::ValueError);
+        }
+        let bw = fraction * self.sample_rate(direction, channel)?;
+        let bw = self
+            .get_bandwidth_range(direction, channel)?
+            .closest(bw)
+            .ok_or(Error::ValueError)?;
+        self.set_bandwidth(direction, channel, bw)
+    }
+
+    /// Apply all parameters that are set in `config` in one call.
+    ///
+    /// Parameters are applied in the order sample rate, bandwidth, frequency, and gain, so that
+    /// drivers re-lock at most once. If a parameter fails, the remaining ones are not applied and
+    /// [`Error::Apply`] reports the name of the failed parameter together with the cause.
+    fn apply_config(
+        &self,
+        direction: Direction,
+        channel: usize,
+        config: &DeviceConfig,
+    ) -> Result<(), Error> {
+        if let Some(rate) = config.sample_rate {
+            self.set_sample_rate(direction, channel, rate)
+                .map_err(failed("sample_rate"))?;
+        }
+        if let Some(bw) = config.bandwidth {
+            self.set_bandwidth(direction, channel, bw)
+                .map_err(failed("bandwidth"))?;
+        }
+        if let Some(frequency) = config.frequency {
+            self.set_frequency(direction, channel, frequency, Args::new())
+                .map_err(failed("frequency"))?;
+        }
+        if let Some(gain) = config.gain {
+            check_gain(self.gain_range(direction, channel), gain, true)
+                .and_then(|g| self.set_gain(direction, channel, g))
+                .map_err(failed("gain"))?;
+        }
+        Ok(())
+    }
+
+    //================================ SENSORS ============================================
+
+    /// List of available sensors, e.g., temperatures or lock indicators.
+    fn sensors(&self) -> Result<Vec<String>, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read the value of the sensor `key` as string.
+    fn read_sensor(&self, key: &str) -> Result<String, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read the value of the sensor `key`, parsed as floating point number.
+    fn read_sensor_f64(&self, key: &str) -> Result<f64, Error> {
+        self.read_sensor(key)?
+            .trim()
+            .parse()
+            .or(Err(Error::ValueError))
+    }
+
+    //================================ CLOCKING ==============================================
+
+    /// Get the current trim value of the reference oscillator, e.g., a VCTCXO trim DAC.
+    fn ref_clock_tune(&self) -> Result<u16, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the trim value of the reference oscillator, e.g., a VCTCXO trim DAC.
+    ///
+    /// This can be used to store and restore a calibrated trim value, for example, in
+    /// GPS-disciplined setups.
+    fn set_ref_clock_tune(&self, trim: u16) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Get the [`Range`] of valid reference oscillator trim values.
+    fn ref_clock_tune_range(&self) -> Result<Range, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// List of available reference clock sources, e.g., `"internal"` or `"external"`.
+    fn clock_sources(&self) -> Result<Vec<String>, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Currently used reference clock source.
+    fn clock_source(&self) -> Result<String, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the reference clock source.
+    ///
+    /// Returns [`Error::ValueError`] if `source` is not one of the [`clock_sources`](Self::clock_sources).
+    fn set_clock_source(&self, source: &str) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    //================================ TIME ==================================================
+
+    /// Get the current hardware time of the device in nanoseconds.
+    ///
+    /// The hardware time is derived from the sample counter of the device, i.e., its effective
+    /// resolution is one sample period (`1e9 / sample_rate` ns), even though it is reported with
+    /// nanosecond granularity. Timed streamer operations, like
+    /// [`RxStreamer::activate_at`](crate::RxStreamer::activate_at), are scheduled on this clock.
+    fn get_hardware_time(&self) -> Result<i64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the hardware time of the device in nanoseconds.
+    ///
+    /// See [`get_hardware_time`](Self::get_hardware_time) for the relationship to the sample rate.
+    fn set_hardware_time(&self, time_ns: i64) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    //================================ STREAM FORMAT =========================================
+
+    /// Sample formats that can be requested for streams through the `stream_format` argument.
+    ///
+    /// [`SampleFormat::CF32`] is always supported.
+    fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        Ok(vec![SampleFormat::CF32])
+    }
+
+    //================================ GPIO ==================================================
+
+    /// List available GPIO banks, e.g., the pins of expansion headers.
+    fn gpio_banks(&self) -> Result<Vec<String>, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read the value of the pins of a GPIO `bank`.
+    ///
+    /// Fails with [`Error::ValueError`] if `bank` is not one of the [`gpio_banks`](DeviceTrait::gpio_banks).
+    fn read_gpio(&self, bank: &str) -> Result<u32, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Write `value` to the pins of a GPIO `bank`, only changing the pins that are set in `mask`.
+    ///
+    /// Fails with [`Error::ValueError`] if `bank` is not one of the [`gpio_banks`](DeviceTrait::gpio_banks).
+    fn write_gpio(&self, bank: &str, value: u32, mask: u32) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    //================================ LOOPBACK ==============================================
+
+    /// List available loopback modes, e.g., `"BB"` or `"RF"`, for testing without antennas.
+    fn loopback_modes(&self) -> Result<Vec<String>, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Set the loopback mode.
+    ///
+    /// `"NONE"` disables loopback. Fails with [`Error::ValueError`] for modes not listed in
+    /// [`loopback_modes`](DeviceTrait::loopback_modes).
+    fn set_loopback(&self, mode: &str) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Get the current loopback mode.
+    fn loopback(&self) -> Result<String, Error> {
+        Err(Error::NotSupported)
+    }
+
+    //================================ CALIBRATION ===========================================
+
+    /// Export the current calibration results in the binary format of [`Calibration`](crate::Calibration).
+    ///
+    /// Can be restored with [`import_calibration`](DeviceTrait::import_calibration), e.g., to avoid
+    /// calibrating the device each time it is opened.
+    fn export_calibration(&self) -> Result<Vec<u8>, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Restore calibration results, exported with [`export_calibration`](DeviceTrait::export_calibration).
+    fn import_calibration(&self, data: &[u8]) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    //================================ CAPABILITIES ==========================================
+
+    /// Snapshot of the capabilities of the device, e.g., to dump them as JSON.
+    ///
+    /// The default implementation queries the other methods of the trait.
+    fn capabilities(&self) -> Result<Capabilities, Error> {
+        Capabilities::from_device(self)
+    }
+
+    //================================ FPGA ==================================================
+
+    /// Load an FPGA image at runtime.
+    ///
+    /// Fails with [`Error::ValueError`] if the size of the `bitstream` does not fit the device.
+    fn load_fpga(&self, bitstream: &[u8]) -> Result<(), Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Whether an FPGA image is loaded.
+    fn fpga_loaded(&self) -> Result<bool, Error> {
+        Err(Error::NotSupported)
+    }
 }
 
 /// Wrapps a driver, implementing the [DeviceTrait].
@@ -208,6 +766,14 @@ pub struct Device<T: DeviceTrait + Clone + Any> {
     dev: T,
 }
 
+impl<T: DeviceTrait + Clone + Any> std::fmt::Debug for Device<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Device")
+            .field("driver", &self.dev.driver())
+            .finish_non_exhaustive()
+    }
+}
+
 impl Device<GenericDevice> {
     /// Creates a [`GenericDevice`] opening the first device discovered through
     /// [`enumerate`](crate::enumerate).
@@ -222,178 +788,199 @@ impl Device<GenericDevice> {
     /// Creates a [`GenericDevice`] opening the first device with a given `driver`, specified in
     /// the `args` or the first device discovered through [`enumerate`](crate::enumerate) that
     /// matches the args.
+    ///
+    /// Without a `driver`, all enabled drivers are tried in order. If none of them found a
+    /// device, [`Error::NotFound`] is returned; if some failed for other reasons,
+    /// [`Error::OpenFailed`] lists the errors of all drivers.
+    ///
+    /// Direction-scoped args, like `rx_sample_rate=3.2e6, tx_frequency=2.4e9`, are applied to all
+    /// channels of the direction after opening the device, see [`DeviceConfig::from_args`]. They
+    /// are ignored if the device has no channels in that direction.
+    ///
+    /// With `temperature_threshold=<degrees>`, a background thread logs a warning each time the
+    /// temperature reported by the sensors of the device drifted by more than the threshold,
+    /// suggesting to re-run the calibration. This is off by default. The thread keeps a weak
+    /// reference to the device, which makes [`impl_mut`](Device::impl_mut) fail.
     pub fn from_args<A: TryInto<Args>>(args: A) -> Result<Self, Error> {
         let args = args.try_into().map_err(|_| Error::ValueError)?;
+        Self::open_args(args, None)
+    }
+
+    /// Creates a [`GenericDevice`] like [`from_args`](Device::from_args), with the `executor`
+    /// and `connector` driving the HTTP requests of the Aaronia HTTP driver.
+    #[cfg(all(feature = "web", not(target_arch = "wasm32")))]
+    pub fn from_args_with_runtime<A: TryInto<Args>, E: crate::Executor, C: crate::Connect>(
+        args: A,
+        executor: E,
+        connector: C,
+    ) -> Result<Self, Error> {
+        let args = args.try_into().map_err(|_| Error::ValueError)?;
+        Self::open_args(
+            args,
+            Some(Box::new(move |args| {
+                Ok(Device {
+                    dev: Arc::new(DeviceWrapper {
+                        dev: crate::impls::AaroniaHttp::open_with_runtime(
+                            args, executor, connector,
+                        )?,
+                    }),
+                })
+            })),
+        )
+    }
+
+    /// Shared implementation of [`from_args`](Device::from_args) and its variants. `http` replaces
+    /// the default opener of the Aaronia HTTP driver.
+    #[cfg_attr(
+        not(all(feature = "aaronia_http", not(target_arch = "wasm32"))),
+        allow(unused_variables)
+    )]
+    fn open_args(args: Args, http: Option<HttpOpener<'_>>) -> Result<Self, Error> {
         let driver = match args.get::<Driver>("driver") {
             Ok(d) => Some(d),
             Err(Error::NotFound) => None,
             Err(e) => return Err(e),
         };
+        let threshold = match args.get::<f64>("temperature_threshold") {
+            Ok(t) if t > 0.0 => Some(t),
+            Ok(_) => return Err(Error::ValueError),
+            Err(Error::NotFound) => None,
+            Err(e) => return Err(e),
+        };
+        let configs = Direction::iter()
+            .map(|d| Ok((d, DeviceConfig::from_args(&args, d)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        if driver.is_some_and(|d| !crate::available_drivers().contains(&d)) {
+            return Err(Error::FeatureNotEnabled);
+        }
+        #[allow(unused_mut)]
+        let mut openers: Vec<(Driver, Opener<'_, Self>)> = Vec::new();
         #[cfg(all(feature = "aaronia", any(target_os = "linux", target_os = "windows")))]
         {
             if driver.is_none() || matches!(driver, Some(Driver::Aaronia)) {
-                match crate::impls::Aaronia::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
+                openers.push((
+                    Driver::Aaronia,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::Aaronia::open(&args)?,
+                            }),
                         })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
+                    }),
+                ));
             }
         }
         #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
         {
             if driver.is_none() || matches!(driver, Some(Driver::AaroniaHttp)) {
-                match crate::impls::AaroniaHttp::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
+                openers.push((
+                    Driver::AaroniaHttp,
+                    Box::new(|| match http {
+                        Some(open) => open(&args),
+                        None => Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::AaroniaHttp::open(&args)?,
+                            }),
+                        }),
+                    }),
+                ));
+            }
+        }
+        #[cfg(feature = "file")]
+        {
+            // software devices are only opened on request
+            if matches!(driver, Some(Driver::File)) {
+                openers.push((
+                    Driver::File,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::FileDevice::open(&args)?,
+                            }),
                         })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
+                    }),
+                ));
+            }
+        }
+        #[cfg(feature = "null")]
+        {
+            // software devices are only opened on request
+            if matches!(driver, Some(Driver::Null)) {
+                openers.push((
+                    Driver::Null,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::NullDevice::open(&args)?,
+                            }),
+                        })
+                    }),
+                ));
+            }
+        }
+        #[cfg(feature = "mock")]
+        {
+            // software devices are only opened on request
+            if matches!(driver, Some(Driver::Mock)) {
+                openers.push((
+                    Driver::Mock,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::MockDevice::open(&args)?,
+                            }),
+                        })
+                    }),
+                ));
             }
         }
         #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
         {
             if driver.is_none() || matches!(driver, Some(Driver::RtlSdr)) {
-                match crate::impls::RtlSdr::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
+                openers.push((
+                    Driver::RtlSdr,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::RtlSdr::open(&args)?,
+                            }),
                         })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
+                    }),
+                ));
             }
         }
         #[cfg(all(feature = "soapy", not(target_arch = "wasm32")))]
         {
             if driver.is_none() || matches!(driver, Some(Driver::Soapy)) {
-                match crate::impls::Soapy::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
+                openers.push((
+                    Driver::Soapy,
+                    Box::new(|| {
+                        Ok(Device {
+                            dev: Arc::new(DeviceWrapper {
+                                dev: crate::impls::Soapy::open(&args)?,
+                            }),
                         })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
+                    }),
+                ));
+            }
+        }
+        let dev = open_first(openers, driver.is_some())?;
+        for (direction, config) in configs {
+            if config != DeviceConfig::default() {
+                for channel in 0..dev.num_channels(direction)? {
+                    dev.apply_config(direction, channel, &config)?;
                 }
             }
         }
-        Err(Error::NotFound)
+        if let Some(t) = threshold {
+            crate::temperature::monitor(&dev.dev, t);
+        }
+        Ok(dev)
     }
+}
 
-    /// Creates a [`GenericDevice`] opening the first device with a given `driver`, specified in
-    /// the `args` or the first device discovered through [`enumerate`](crate::enumerate) that
-    /// matches the args.
-    #[cfg(all(feature = "web", not(target_arch = "wasm32")))]
-    pub fn from_args_with_runtime<A: TryInto<Args>, E: crate::Executor, C: crate::Connect>(
-        args: A,
-        executor: E,
-        connector: C,
-    ) -> Result<Self, Error> {
-        let args = args.try_into().or(Err(Error::ValueError))?;
-        let driver = match args.get::<Driver>("driver") {
-            Ok(d) => Some(d),
-            Err(Error::NotFound) => None,
-            Err(e) => return Err(e),
-        };
-        #[cfg(all(feature = "aaronia", any(target_os = "linux", target_os = "windows")))]
-        {
-            if driver.is_none() || matches!(driver, Some(Driver::Aaronia)) {
-                match crate::impls::Aaronia::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
-                        })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
-            }
-        }
-        #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
-        {
-            if driver.is_none() || matches!(driver, Some(Driver::AaroniaHttp)) {
-                match crate::impls::AaroniaHttp::open_with_runtime(&args, executor, connector) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
-                        })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
-            }
-        }
-        #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
-        {
-            if driver.is_none() || matches!(driver, Some(Driver::RtlSdr)) {
-                match crate::impls::RtlSdr::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
-                        })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
-            }
-        }
-        #[cfg(all(feature = "soapy", not(target_arch = "wasm32")))]
-        {
-            if driver.is_none() || matches!(driver, Some(Driver::Soapy)) {
-                match crate::impls::Soapy::open(&args) {
-                    Ok(d) => {
-                        return Ok(Device {
-                            dev: Arc::new(DeviceWrapper { dev: d }),
-                        })
-                    }
-                    Err(Error::NotFound) => {
-                        if driver.is_some() {
-                            return Err(Error::NotFound);
-                        }
-                    }
-                    Err(e) => return Err(e),
-                }
-            }
-        }
-        Err(Error::NotFound)
-    }
-}
+/// Opener of the Aaronia HTTP driver that replaces its default one in `Device::open_args`.
+type HttpOpener<'a> = Box<dyn FnOnce(&Args) -> Result<Device<GenericDevice>, Error> + 'a>;
 
 /// Type for a generic/wrapped hardware driver, implementing the [`DeviceTrait`].
 ///
@@ -411,6 +998,23 @@ impl<T: DeviceTrait + Clone + Any> Device<T> {
     }
     /// Try to downcast to a given device implementation `D`, either directly (from `Device<D>`)
     /// or indirectly (from a `Device<GenericDevice>` that wraps a `D`).
+    ///
+    /// Returns [`Error::ValueError`] if the device is not a `D`.
+    ///
+    /// ```no_run
+    /// # #[cfg(all(feature = "rtlsdr", feature = "aaronia_http", not(target_arch = "wasm32")))]
+    /// # fn main() -> Result<(), seify::Error> {
+    /// use seify::impls::{AaroniaHttp, RtlSdr};
+    /// use seify::Device;
+    ///
+    /// let dev = Device::from_args("driver=rtlsdr")?;
+    /// let rtl: &RtlSdr = dev.impl_ref()?;
+    /// assert!(dev.impl_ref::<AaroniaHttp>().is_err());
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(all(feature = "rtlsdr", feature = "aaronia_http", not(target_arch = "wasm32"))))]
+    /// # fn main() {}
+    /// ```
     pub fn impl_ref<D: DeviceTrait + Any>(&self) -> Result<&D, Error> {
         if let Some(d) = self.dev.as_any().downcast_ref::<D>() {
             return Ok(d);
@@ -436,6 +1040,11 @@ impl<T: DeviceTrait + Clone + Any> Device<T> {
     }
     /// Try to downcast mutably to a given device implementation `D`, either directly
     /// (from `Device<D>`) or indirectly (from a `Device<GenericDevice>` that wraps a `D`).
+    ///
+    /// For a `Device<GenericDevice>`, this only succeeds as long as the device is not shared,
+    /// i.e., if there are no clones of it. The temperature monitor that is started with the
+    /// `temperature_threshold` argument of [`from_args`](Device::from_args) holds a weak
+    /// reference, so this always fails for such devices.
     pub fn impl_mut<D: DeviceTrait + Any>(&mut self) -> Result<&mut D, Error> {
         // work around borrow checker limitation
         if let Some(d) = self.dev.as_any().downcast_ref::<D>() {
@@ -444,15 +1053,11 @@ impl<T: DeviceTrait + Clone + Any> Device<T> {
             let d = self
                 .dev
                 .as_any_mut()
-                .downcast_mut::<Box<
-                    (dyn DeviceTrait<
-                        RxStreamer = Box<(dyn RxStreamer + 'static)>,
-                        TxStreamer = Box<(dyn TxStreamer + 'static)>,
-                    > + 'static),
-                >>()
+                .downcast_mut::<GenericDevice>()
                 .ok_or(Error::ValueError)?;
 
-            let d = (**d)
+            let d = Arc::get_mut(d)
+                .ok_or(Error::ValueError)?
                 .as_any_mut()
                 .downcast_mut::<DeviceWrapper<D>>()
                 .ok_or(Error::ValueError)?;
@@ -490,6 +1095,11 @@ impl<
     fn info(&self) -> Result<Args, Error> {
         self.dev.info()
     }
+
+    fn info_refresh(&self) -> Result<Args, Error> {
+        self.dev.info_refresh()
+    }
+
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
         self.dev.num_channels(direction)
     }
@@ -497,11 +1107,52 @@ impl<
         self.dev.full_duplex(direction, channel)
     }
 
+    fn reset(&self) -> Result<(), Error> {
+        self.dev.reset()
+    }
+
+    fn try_clone(&self) -> Result<GenericDevice, Error> {
+        self.dev.try_clone()
+    }
+
+    fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.dev.set_feature(key, value)
+    }
+
+    fn feature(&self, key: &str) -> Result<String, Error> {
+        self.dev.feature(key)
+    }
+
+    #[cfg(feature = "raw-registers")]
+    fn read_register(&self, addr: u16) -> Result<u32, Error> {
+        self.dev.read_register(addr)
+    }
+
+    #[cfg(feature = "raw-registers")]
+    fn write_register(&self, addr: u16, value: u32) -> Result<(), Error> {
+        self.dev.write_register(addr, value)
+    }
+
     fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
-        Ok(Box::new(self.dev.rx_streamer(channels, args)?))
+        let s = self.dev.rx_streamer(channels, args)?;
+        #[cfg(feature = "metrics")]
+        let s = crate::metered::Metered::rx(s, self.dev.driver());
+        Ok(Box::new(s))
     }
     fn tx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::TxStreamer, Error> {
-        Ok(Box::new(self.dev.tx_streamer(channels, args)?))
+        let s = self.dev.tx_streamer(channels, args)?;
+        #[cfg(feature = "metrics")]
+        let s = crate::metered::Metered::tx(s, self.dev.driver());
+        Ok(Box::new(s))
+    }
+
+    fn set_channel_enabled(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.dev.set_channel_enabled(direction, channel, enabled)
     }
 
     fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
@@ -516,6 +1167,19 @@ impl<
         self.dev.set_antenna(direction, channel, name)
     }
 
+    fn set_bias_tee(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.dev.set_bias_tee(direction, channel, enabled)
+    }
+
+    fn bias_tee(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.dev.bias_tee(direction, channel)
+    }
+
     fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         self.dev.gain_elements(direction, channel)
     }
@@ -532,6 +1196,57 @@ impl<
         self.dev.agc(direction, channel)
     }
 
+    fn set_agc_setpoint(
+        &self,
+        direction: Direction,
+        channel: usize,
+        dbfs: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_setpoint(direction, channel, dbfs)
+    }
+
+    fn agc_setpoint(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_setpoint(direction, channel)
+    }
+
+    fn agc_setpoint_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_setpoint_range(direction, channel)
+    }
+
+    fn set_agc_attack(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_attack(direction, channel, seconds)
+    }
+
+    fn agc_attack(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_attack(direction, channel)
+    }
+
+    fn agc_attack_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_attack_range(direction, channel)
+    }
+
+    fn set_agc_decay(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_decay(direction, channel, seconds)
+    }
+
+    fn agc_decay(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_decay(direction, channel)
+    }
+
+    fn agc_decay_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_decay_range(direction, channel)
+    }
+
     fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
         self.dev.set_gain(direction, channel, gain)
     }
@@ -544,6 +1259,36 @@ impl<
         self.dev.gain_range(direction, channel)
     }
 
+    fn min_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.min_gain(direction, channel)
+    }
+
+    fn max_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.max_gain(direction, channel)
+    }
+
+    fn set_gain_normalized(
+        &self,
+        direction: Direction,
+        channel: usize,
+        value: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_gain_normalized(direction, channel, value)
+    }
+
+    fn gain_normalized(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.dev.gain_normalized(direction, channel)
+    }
+
+    fn set_gain_distributed(
+        &self,
+        direction: Direction,
+        channel: usize,
+        total_db: f64,
+    ) -> Result<f64, Error> {
+        self.dev.set_gain_distributed(direction, channel, total_db)
+    }
+
     fn set_gain_element(
         &self,
         direction: Direction,
@@ -572,6 +1317,10 @@ impl<
         self.dev.gain_element_range(direction, channel, name)
     }
 
+    fn set_gains(&self, direction: Direction, channel: usize, gains: &[Gain]) -> Result<(), Error> {
+        self.dev.set_gains(direction, channel, gains)
+    }
+
     fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
         self.dev.frequency_range(direction, channel)
     }
@@ -590,6 +1339,20 @@ impl<
         self.dev.set_frequency(direction, channel, frequency, args)
     }
 
+    fn tune(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        offset: f64,
+    ) -> Result<(), Error> {
+        self.dev.tune(direction, channel, frequency, offset)
+    }
+
+    fn tune_settling_time(&self, direction: Direction, channel: usize) -> Result<Duration, Error> {
+        self.dev.tune_settling_time(direction, channel)
+    }
+
     fn frequency_components(
         &self,
         direction: Direction,
@@ -643,6 +1406,128 @@ impl<
     fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
         self.dev.get_sample_rate_range(direction, channel)
     }
+
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.bandwidth(direction, channel)
+    }
+
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        self.dev.set_bandwidth(direction, channel, bw)
+    }
+
+    fn get_bandwidth_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.get_bandwidth_range(direction, channel)
+    }
+
+    fn set_bandwidth_auto(
+        &self,
+        direction: Direction,
+        channel: usize,
+        fraction: Option<f64>,
+    ) -> Result<(), Error> {
+        self.dev.set_bandwidth_auto(direction, channel, fraction)
+    }
+
+    fn apply_config(
+        &self,
+        direction: Direction,
+        channel: usize,
+        config: &DeviceConfig,
+    ) -> Result<(), Error> {
+        self.dev.apply_config(direction, channel, config)
+    }
+
+    fn sensors(&self) -> Result<Vec<String>, Error> {
+        self.dev.sensors()
+    }
+
+    fn read_sensor(&self, key: &str) -> Result<String, Error> {
+        self.dev.read_sensor(key)
+    }
+
+    fn read_sensor_f64(&self, key: &str) -> Result<f64, Error> {
+        self.dev.read_sensor_f64(key)
+    }
+
+    fn ref_clock_tune(&self) -> Result<u16, Error> {
+        self.dev.ref_clock_tune()
+    }
+
+    fn set_ref_clock_tune(&self, trim: u16) -> Result<(), Error> {
+        self.dev.set_ref_clock_tune(trim)
+    }
+
+    fn ref_clock_tune_range(&self) -> Result<Range, Error> {
+        self.dev.ref_clock_tune_range()
+    }
+
+    fn clock_sources(&self) -> Result<Vec<String>, Error> {
+        self.dev.clock_sources()
+    }
+
+    fn clock_source(&self) -> Result<String, Error> {
+        self.dev.clock_source()
+    }
+
+    fn set_clock_source(&self, source: &str) -> Result<(), Error> {
+        self.dev.set_clock_source(source)
+    }
+
+    fn get_hardware_time(&self) -> Result<i64, Error> {
+        self.dev.get_hardware_time()
+    }
+
+    fn set_hardware_time(&self, time_ns: i64) -> Result<(), Error> {
+        self.dev.set_hardware_time(time_ns)
+    }
+
+    fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        self.dev.supported_formats(direction)
+    }
+
+    fn gpio_banks(&self) -> Result<Vec<String>, Error> {
+        self.dev.gpio_banks()
+    }
+
+    fn read_gpio(&self, bank: &str) -> Result<u32, Error> {
+        self.dev.read_gpio(bank)
+    }
+
+    fn write_gpio(&self, bank: &str, value: u32, mask: u32) -> Result<(), Error> {
+        self.dev.write_gpio(bank, value, mask)
+    }
+
+    fn loopback_modes(&self) -> Result<Vec<String>, Error> {
+        self.dev.loopback_modes()
+    }
+
+    fn set_loopback(&self, mode: &str) -> Result<(), Error> {
+        self.dev.set_loopback(mode)
+    }
+
+    fn loopback(&self) -> Result<String, Error> {
+        self.dev.loopback()
+    }
+
+    fn export_calibration(&self) -> Result<Vec<u8>, Error> {
+        self.dev.export_calibration()
+    }
+
+    fn import_calibration(&self, data: &[u8]) -> Result<(), Error> {
+        self.dev.import_calibration(data)
+    }
+
+    fn capabilities(&self) -> Result<Capabilities, Error> {
+        self.dev.capabilities()
+    }
+
+    fn load_fpga(&self, bitstream: &[u8]) -> Result<(), Error> {
+        self.dev.load_fpga(bitstream)
+    }
+
+    fn fpga_loaded(&self) -> Result<bool, Error> {
+        self.dev.fpga_loaded()
+    }
 }
 
 #[doc(hidden)]
@@ -667,6 +1552,11 @@ impl DeviceTrait for GenericDevice {
     fn info(&self) -> Result<Args, Error> {
         self.as_ref().info()
     }
+
+    fn info_refresh(&self) -> Result<Args, Error> {
+        self.as_ref().info_refresh()
+    }
+
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
         self.as_ref().num_channels(direction)
     }
@@ -674,6 +1564,32 @@ impl DeviceTrait for GenericDevice {
         self.as_ref().full_duplex(direction, channel)
     }
 
+    fn reset(&self) -> Result<(), Error> {
+        self.as_ref().reset()
+    }
+
+    fn try_clone(&self) -> Result<GenericDevice, Error> {
+        self.as_ref().try_clone()
+    }
+
+    fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.as_ref().set_feature(key, value)
+    }
+
+    fn feature(&self, key: &str) -> Result<String, Error> {
+        self.as_ref().feature(key)
+    }
+
+    #[cfg(feature = "raw-registers")]
+    fn read_register(&self, addr: u16) -> Result<u32, Error> {
+        self.as_ref().read_register(addr)
+    }
+
+    #[cfg(feature = "raw-registers")]
+    fn write_register(&self, addr: u16, value: u32) -> Result<(), Error> {
+        self.as_ref().write_register(addr, value)
+    }
+
     fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
         Ok(Box::new(self.as_ref().rx_streamer(channels, args)?))
     }
@@ -682,6 +1598,16 @@ impl DeviceTrait for GenericDevice {
         Ok(Box::new(self.as_ref().tx_streamer(channels, args)?))
     }
 
+    fn set_channel_enabled(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.as_ref()
+            .set_channel_enabled(direction, channel, enabled)
+    }
+
     fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         self.as_ref().antennas(direction, channel)
     }
@@ -694,6 +1620,19 @@ impl DeviceTrait for GenericDevice {
         self.as_ref().set_antenna(direction, channel, name)
     }
 
+    fn set_bias_tee(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.as_ref().set_bias_tee(direction, channel, enabled)
+    }
+
+    fn bias_tee(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.as_ref().bias_tee(direction, channel)
+    }
+
     fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         self.as_ref().gain_elements(direction, channel)
     }
@@ -710,48 +1649,134 @@ impl DeviceTrait for GenericDevice {
         self.as_ref().agc(direction, channel)
     }
 
-    fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
-        self.as_ref().set_gain(direction, channel, gain)
+    fn set_agc_setpoint(
+        &self,
+        direction: Direction,
+        channel: usize,
+        dbfs: f64,
+    ) -> Result<(), Error> {
+        self.as_ref().set_agc_setpoint(direction, channel, dbfs)
     }
 
-    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
-        self.as_ref().gain(direction, channel)
+    fn agc_setpoint(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().agc_setpoint(direction, channel)
     }
 
-    fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
-        self.as_ref().gain_range(direction, channel)
+    fn agc_setpoint_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.as_ref().agc_setpoint_range(direction, channel)
     }
 
-    fn set_gain_element(
+    fn set_agc_attack(
         &self,
         direction: Direction,
         channel: usize,
-        name: &str,
-        gain: f64,
+        seconds: f64,
     ) -> Result<(), Error> {
-        self.as_ref()
-            .set_gain_element(direction, channel, name, gain)
+        self.as_ref().set_agc_attack(direction, channel, seconds)
     }
 
-    fn gain_element(
-        &self,
-        direction: Direction,
-        channel: usize,
-        name: &str,
-    ) -> Result<Option<f64>, Error> {
-        self.as_ref().gain_element(direction, channel, name)
+    fn agc_attack(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().agc_attack(direction, channel)
     }
 
-    fn gain_element_range(
+    fn agc_attack_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.as_ref().agc_attack_range(direction, channel)
+    }
+
+    fn set_agc_decay(
         &self,
         direction: Direction,
         channel: usize,
-        name: &str,
-    ) -> Result<Range, Error> {
-        self.as_ref().gain_element_range(direction, channel, name)
+        seconds: f64,
+    ) -> Result<(), Error> {
+        self.as_ref().set_agc_decay(direction, channel, seconds)
     }
 
-    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+    fn agc_decay(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().agc_decay(direction, channel)
+    }
+
+    fn agc_decay_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.as_ref().agc_decay_range(direction, channel)
+    }
+
+    fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
+        self.as_ref().set_gain(direction, channel, gain)
+    }
+
+    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.as_ref().gain(direction, channel)
+    }
+
+    fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.as_ref().gain_range(direction, channel)
+    }
+
+    fn min_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().min_gain(direction, channel)
+    }
+
+    fn max_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().max_gain(direction, channel)
+    }
+
+    fn set_gain_normalized(
+        &self,
+        direction: Direction,
+        channel: usize,
+        value: f64,
+    ) -> Result<(), Error> {
+        self.as_ref().set_gain_normalized(direction, channel, value)
+    }
+
+    fn gain_normalized(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.as_ref().gain_normalized(direction, channel)
+    }
+
+    fn set_gain_distributed(
+        &self,
+        direction: Direction,
+        channel: usize,
+        total_db: f64,
+    ) -> Result<f64, Error> {
+        self.as_ref()
+            .set_gain_distributed(direction, channel, total_db)
+    }
+
+    fn set_gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        gain: f64,
+    ) -> Result<(), Error> {
+        self.as_ref()
+            .set_gain_element(direction, channel, name, gain)
+    }
+
+    fn gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Option<f64>, Error> {
+        self.as_ref().gain_element(direction, channel, name)
+    }
+
+    fn gain_element_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        self.as_ref().gain_element_range(direction, channel, name)
+    }
+
+    fn set_gains(&self, direction: Direction, channel: usize, gains: &[Gain]) -> Result<(), Error> {
+        self.as_ref().set_gains(direction, channel, gains)
+    }
+
+    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
         self.as_ref().frequency_range(direction, channel)
     }
 
@@ -770,6 +1795,20 @@ impl DeviceTrait for GenericDevice {
             .set_frequency(direction, channel, frequency, args)
     }
 
+    fn tune(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        offset: f64,
+    ) -> Result<(), Error> {
+        self.as_ref().tune(direction, channel, frequency, offset)
+    }
+
+    fn tune_settling_time(&self, direction: Direction, channel: usize) -> Result<Duration, Error> {
+        self.as_ref().tune_settling_time(direction, channel)
+    }
+
     fn frequency_components(
         &self,
         direction: Direction,
@@ -824,6 +1863,129 @@ impl DeviceTrait for GenericDevice {
     fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
         self.as_ref().get_sample_rate_range(direction, channel)
     }
+
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.as_ref().bandwidth(direction, channel)
+    }
+
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        self.as_ref().set_bandwidth(direction, channel, bw)
+    }
+
+    fn get_bandwidth_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.as_ref().get_bandwidth_range(direction, channel)
+    }
+
+    fn set_bandwidth_auto(
+        &self,
+        direction: Direction,
+        channel: usize,
+        fraction: Option<f64>,
+    ) -> Result<(), Error> {
+        self.as_ref()
+            .set_bandwidth_auto(direction, channel, fraction)
+    }
+
+    fn apply_config(
+        &self,
+        direction: Direction,
+        channel: usize,
+        config: &DeviceConfig,
+    ) -> Result<(), Error> {
+        self.as_ref().apply_config(direction, channel, config)
+    }
+
+    fn sensors(&self) -> Result<Vec<String>, Error> {
+        self.as_ref().sensors()
+    }
+
+    fn read_sensor(&self, key: &str) -> Result<String, Error> {
+        self.as_ref().read_sensor(key)
+    }
+
+    fn read_sensor_f64(&self, key: &str) -> Result<f64, Error> {
+        self.as_ref().read_sensor_f64(key)
+    }
+
+    fn ref_clock_tune(&self) -> Result<u16, Error> {
+        self.as_ref().ref_clock_tune()
+    }
+
+    fn set_ref_clock_tune(&self, trim: u16) -> Result<(), Error> {
+        self.as_ref().set_ref_clock_tune(trim)
+    }
+
+    fn ref_clock_tune_range(&self) -> Result<Range, Error> {
+        self.as_ref().ref_clock_tune_range()
+    }
+
+    fn clock_sources(&self) -> Result<Vec<String>, Error> {
+        self.as_ref().clock_sources()
+    }
+
+    fn clock_source(&self) -> Result<String, Error> {
+        self.as_ref().clock_source()
+    }
+
+    fn set_clock_source(&self, source: &str) -> Result<(), Error> {
+        self.as_ref().set_clock_source(source)
+    }
+
+    fn get_hardware_time(&self) -> Result<i64, Error> {
+        self.as_ref().get_hardware_time()
+    }
+
+    fn set_hardware_time(&self, time_ns: i64) -> Result<(), Error> {
+        self.as_ref().set_hardware_time(time_ns)
+    }
+
+    fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        self.as_ref().supported_formats(direction)
+    }
+
+    fn gpio_banks(&self) -> Result<Vec<String>, Error> {
+        self.as_ref().gpio_banks()
+    }
+
+    fn read_gpio(&self, bank: &str) -> Result<u32, Error> {
+        self.as_ref().read_gpio(bank)
+    }
+
+    fn write_gpio(&self, bank: &str, value: u32, mask: u32) -> Result<(), Error> {
+        self.as_ref().write_gpio(bank, value, mask)
+    }
+
+    fn loopback_modes(&self) -> Result<Vec<String>, Error> {
+        self.as_ref().loopback_modes()
+    }
+
+    fn set_loopback(&self, mode: &str) -> Result<(), Error> {
+        self.as_ref().set_loopback(mode)
+    }
+
+    fn loopback(&self) -> Result<String, Error> {
+        self.as_ref().loopback()
+    }
+
+    fn export_calibration(&self) -> Result<Vec<u8>, Error> {
+        self.as_ref().export_calibration()
+    }
+
+    fn import_calibration(&self, data: &[u8]) -> Result<(), Error> {
+        self.as_ref().import_calibration(data)
+    }
+
+    fn capabilities(&self) -> Result<Capabilities, Error> {
+        self.as_ref().capabilities()
+    }
+
+    fn load_fpga(&self, bitstream: &[u8]) -> Result<(), Error> {
+        self.as_ref().load_fpga(bitstream)
+    }
+
+    fn fpga_loaded(&self) -> Result<bool, Error> {
+        self.as_ref().fpga_loaded()
+    }
 }
 
 impl<
@@ -844,6 +2006,11 @@ impl<
     pub fn info(&self) -> Result<Args, Error> {
         self.dev.info()
     }
+    /// Query the device info from the hardware again, updating the cached copy returned by
+    /// [`info`](Self::info).
+    pub fn info_refresh(&self) -> Result<Args, Error> {
+        self.dev.info_refresh()
+    }
     /// Number of supported Channels.
     pub fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
         self.dev.num_channels(direction)
@@ -853,6 +2020,58 @@ impl<
         self.dev.full_duplex(direction, channel)
     }
 
+    /// Reset and re-initialize the device, e.g., to recover from a wedged USB connection.
+    ///
+    /// Open streamers become invalid and have to be recreated. Settings are restored to the state
+    /// after opening the device.
+    pub fn reset(&self) -> Result<(), Error> {
+        self.dev.reset()
+    }
+
+    /// Open a second, independent handle to the same device, see
+    /// [`DeviceTrait::try_clone`].
+    pub fn try_clone(&self) -> Result<Device<GenericDevice>, Error> {
+        Ok(Device::from_impl(self.dev.try_clone()?))
+    }
+
+    /// Set a driver-specific feature that is not covered by the generic API.
+    ///
+    /// This is an extension point for hardware specifics, e.g., enabling an expansion board with
+    /// `("expansion_board", "xb200")`. Unknown keys return [`Error::NotSupported`], invalid values
+    /// [`Error::ValueError`].
+    pub fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.dev.set_feature(key, value)
+    }
+
+    /// Read back a driver-specific feature, see [`set_feature`](Self::set_feature).
+    pub fn feature(&self, key: &str) -> Result<String, Error> {
+        self.dev.feature(key)
+    }
+
+    /// Read the raw register at `addr` of the device.
+    ///
+    /// Only available with the `raw-registers` feature, as an escape hatch for experiments that are not
+    /// covered by the generic API. The address space is driver-specific.
+    #[cfg(feature = "raw-registers")]
+    pub fn read_register(&self, addr: u16) -> Result<u32, Error> {
+        self.dev.read_register(addr)
+    }
+
+    /// Write `value` to the raw register at `addr` of the device.
+    ///
+    /// Only available with the `raw-registers` feature, see [`read_register`](Self::read_register).
+    ///
+    /// # Warning
+    ///
+    /// This bypasses all driver state and checks. Writing a register can put the hardware in a
+    /// state that is inconsistent with the settings cached by the driver, stop it from working
+    /// until it is power cycled, or damage attached equipment. Only use it if you know the register
+    /// map of the device.
+    #[cfg(feature = "raw-registers")]
+    pub fn write_register(&self, addr: u16, value: u32) -> Result<(), Error> {
+        self.dev.write_register(addr, value)
+    }
+
     //================================ STREAMER ============================================
     /// Create an RX streamer.
     pub fn rx_streamer(&self, channels: &[usize]) -> Result<R, Error> {
@@ -866,6 +2085,16 @@ impl<
     pub fn tx_streamer(&self, channels: &[usize]) -> Result<T, Error> {
         self.dev.tx_streamer(channels, Args::new())
     }
+
+    /// Enable or disable the RF chain of a channel, without tearing down its streamer.
+    pub fn set_channel_enabled(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.dev.set_channel_enabled(direction, channel, enabled)
+    }
     /// Create a TX Streamer, using `args`.
     pub fn tx_streamer_with_args(&self, channels: &[usize], args: Args) -> Result<T, Error> {
         self.dev.tx_streamer(channels, args)
@@ -890,6 +2119,23 @@ impl<
         self.dev.set_antenna(direction, channel, name)
     }
 
+    /// Enable or disable the bias tee, powering external components like LNAs through the antenna port.
+    ///
+    /// Drivers supporting it also accept a `bias_tee` argument when opening the device.
+    pub fn set_bias_tee(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.dev.set_bias_tee(direction, channel, enabled)
+    }
+
+    /// Whether the bias tee is enabled.
+    pub fn bias_tee(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.dev.bias_tee(direction, channel)
+    }
+
     //================================ AGC ============================================
     /// Does the device support automatic gain control?
     pub fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
@@ -904,6 +2150,74 @@ impl<
         self.dev.agc(direction, channel)
     }
 
+    /// Set the target level of the automatic gain control in dBFS.
+    ///
+    /// Valid values are given by [`agc_setpoint_range`](Self::agc_setpoint_range).
+    pub fn set_agc_setpoint(
+        &self,
+        direction: Direction,
+        channel: usize,
+        dbfs: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_setpoint(direction, channel, dbfs)
+    }
+
+    /// Target level of the automatic gain control in dBFS.
+    pub fn agc_setpoint(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_setpoint(direction, channel)
+    }
+
+    /// Range of possible AGC target levels in dBFS.
+    pub fn agc_setpoint_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_setpoint_range(direction, channel)
+    }
+
+    /// Set the attack time of the automatic gain control in seconds, i.e., how fast the gain is
+    /// reduced when the signal exceeds the setpoint.
+    ///
+    /// Valid values are given by [`agc_attack_range`](Self::agc_attack_range).
+    pub fn set_agc_attack(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_attack(direction, channel, seconds)
+    }
+
+    /// Attack time of the automatic gain control in seconds.
+    pub fn agc_attack(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_attack(direction, channel)
+    }
+
+    /// Range of possible AGC attack times in seconds.
+    pub fn agc_attack_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_attack_range(direction, channel)
+    }
+
+    /// Set the decay time of the automatic gain control in seconds, i.e., how fast the gain is
+    /// increased when the signal falls below the setpoint.
+    ///
+    /// Valid values are given by [`agc_decay_range`](Self::agc_decay_range).
+    pub fn set_agc_decay(
+        &self,
+        direction: Direction,
+        channel: usize,
+        seconds: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_agc_decay(direction, channel, seconds)
+    }
+
+    /// Decay time of the automatic gain control in seconds.
+    pub fn agc_decay(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.agc_decay(direction, channel)
+    }
+
+    /// Range of possible AGC decay times in seconds.
+    pub fn agc_decay_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.dev.agc_decay_range(direction, channel)
+    }
+
     //================================ GAIN ============================================
     /// List of available gain elements.
     ///
@@ -921,7 +2235,37 @@ impl<
     /// The gain will be distributed automatically across available elements.
     ///
     /// `gain`: the new amplification value in dB
+    ///
+    /// Values outside of the [`gain_range`](Self::gain_range) are clamped to the closest valid
+    /// gain, logging a warning. Use [`try_set_gain`](Self::try_set_gain) to get an error instead.
     pub fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
+        let gain = check_gain(self.dev.gain_range(direction, channel), gain, true)?;
+        self.dev.set_gain(direction, channel, gain)
+    }
+
+    /// Set the overall amplification in a chain and read back the gain that was actually applied.
+    ///
+    /// Hardware often quantizes the gain, so the result can deviate from `gain`. Like
+    /// [`gain`](Self::gain), returns `None` if the gain is controlled by the AGC.
+    pub fn set_gain_actual(
+        &self,
+        direction: Direction,
+        channel: usize,
+        gain: f64,
+    ) -> Result<Option<f64>, Error> {
+        self.set_gain(direction, channel, gain)?;
+        self.dev.gain(direction, channel)
+    }
+
+    /// Set the overall amplification in a chain, failing with [`Error::OutOfRange`] if `gain` is
+    /// outside of the [`gain_range`](Self::gain_range).
+    pub fn try_set_gain(
+        &self,
+        direction: Direction,
+        channel: usize,
+        gain: f64,
+    ) -> Result<(), Error> {
+        let gain = check_gain(self.dev.gain_range(direction, channel), gain, false)?;
         self.dev.set_gain(direction, channel, gain)
     }
 
@@ -935,6 +2279,54 @@ impl<
         self.dev.gain_range(direction, channel)
     }
 
+    /// Smallest overall gain in dB, from the [`gain_range`](Self::gain_range).
+    pub fn min_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.min_gain(direction, channel)
+    }
+
+    /// Largest overall gain in dB, from the [`gain_range`](Self::gain_range).
+    pub fn max_gain(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.max_gain(direction, channel)
+    }
+
+    /// Set the overall amplification in a chain on a normalized scale.
+    ///
+    /// `value` is mapped linearly from `0.0..=1.0` onto the [`gain_range`](Device::gain_range),
+    /// snapping to the closest supported gain.
+    pub fn set_gain_normalized(
+        &self,
+        direction: Direction,
+        channel: usize,
+        value: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_gain_normalized(direction, channel, value)
+    }
+
+    /// Get the overall amplification in a chain on a normalized scale of `0.0..=1.0`.
+    ///
+    /// See [`set_gain_normalized`](Device::set_gain_normalized).
+    pub fn gain_normalized(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Option<f64>, Error> {
+        self.dev.gain_normalized(direction, channel)
+    }
+
+    /// Distribute an overall gain across the individual gain elements.
+    ///
+    /// Elements are filled in the order of [`gain_elements`](Device::gain_elements), i.e., stages close
+    /// to the antenna get their gain first. Returns the total gain that was achieved, which might differ
+    /// from `total_db`, if it is outside the combined range or not a possible step.
+    pub fn set_gain_distributed(
+        &self,
+        direction: Direction,
+        channel: usize,
+        total_db: f64,
+    ) -> Result<f64, Error> {
+        self.dev.set_gain_distributed(direction, channel, total_db)
+    }
+
     /// Set the value of a amplification element in a chain.
     ///
     /// ## Arguments
@@ -970,6 +2362,20 @@ impl<
         self.dev.gain_element_range(direction, channel, name)
     }
 
+    /// Apply a batch of overall and per-stage gains in order.
+    ///
+    /// All values are checked against their ranges before any gain is set, so that an invalid batch
+    /// does not leave the chain partially configured. If the device rejects a gain nevertheless,
+    /// [`Error::Apply`] reports the name of the failed gain, and the gains before it stay applied.
+    pub fn set_gains(
+        &self,
+        direction: Direction,
+        channel: usize,
+        gains: &[Gain],
+    ) -> Result<(), Error> {
+        self.dev.set_gains(direction, channel, gains)
+    }
+
     //================================ FREQUENCY ============================================
 
     /// Get the ranges of overall frequency values.
@@ -1006,6 +2412,68 @@ impl<
             .set_frequency(direction, channel, frequency, Args::new())
     }
 
+    /// Like [`set_frequency`](Self::set_frequency) but block until the device has settled.
+    ///
+    /// Waits for the [`tune_settling_time`](Self::tune_settling_time) after tuning. If the driver
+    /// does not provide a settling time, this returns immediately after tuning.
+    pub fn set_frequency_settled(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+    ) -> Result<(), Error> {
+        self.set_frequency(direction, channel, frequency)?;
+        match self.dev.tune_settling_time(direction, channel) {
+            Ok(d) => std::thread::sleep(d),
+            Err(Error::NotSupported) => {}
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    /// Tune to `frequency` and read back the frequency that was actually tuned.
+    ///
+    /// Hardware can often only hit discrete frequencies. Returns [`Error::OutOfRange`] with the
+    /// accepted range and the actual frequency, if it deviates from `frequency` by more than
+    /// `tol_hz`.
+    pub fn set_frequency_checked(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        tol_hz: f64,
+    ) -> Result<f64, Error> {
+        self.set_frequency(direction, channel, frequency)?;
+        check_tolerance(frequency, self.dev.frequency(direction, channel)?, tol_hz)
+    }
+
+    /// Tune to `frequency`, placing the first ("RF") component `offset` Hz below it.
+    ///
+    /// The last (baseband) component compensates for the `offset`, so that the overall frequency
+    /// matches. This moves the DC spur of the LO away from the signal of interest. Components in
+    /// between are set to zero. Devices with a single component only support an `offset` of zero.
+    pub fn tune(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        offset: f64,
+    ) -> Result<(), Error> {
+        self.dev.tune(direction, channel, frequency, offset)
+    }
+
+    /// Time it takes after a frequency change until received or transmitted samples are valid.
+    ///
+    /// This is a conservative hint for applications that hop quickly over frequencies, e.g.,
+    /// scanners.
+    pub fn tune_settling_time(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Duration, Error> {
+        self.dev.tune_settling_time(direction, channel)
+    }
+
     /// Like [`set_frequency`](Self::set_frequency) but using `args` to augment the tuning algorithm.
     ///
     ///   - Use `"OFFSET"` to specify an "RF" tuning offset,
@@ -1032,7 +2500,8 @@ impl<
 
     /// List available tunable elements in the chain.
     ///
-    /// Elements should be in order RF to baseband.
+    /// Elements should be in order RF to baseband. The overall frequency is the sum of the
+    /// frequencies of all components.
     pub fn frequency_components(
         &self,
         direction: Direction,
@@ -1084,13 +2553,35 @@ impl<
     }
 
     /// Set the baseband sample rate of the chain in samples per second.
+    ///
+    /// Logs a warning if the resulting bandwidth exceeds the sample rate, which causes aliasing.
+    /// Use [`set_bandwidth_auto`](Self::set_bandwidth_auto) to adapt the bandwidth.
     pub fn set_sample_rate(
         &self,
         direction: Direction,
         channel: usize,
         rate: f64,
     ) -> Result<(), Error> {
-        self.dev.set_sample_rate(direction, channel, rate)
+        self.dev.set_sample_rate(direction, channel, rate)?;
+        self.check_bandwidth(direction, channel);
+        Ok(())
+    }
+
+    /// Set the sample rate and read back the rate that was actually configured.
+    ///
+    /// Returns [`Error::OutOfRange`] with the accepted range and the actual rate, if it deviates
+    /// from `rate` by more than `tolerance` samples per second. Like
+    /// [`set_sample_rate`](Self::set_sample_rate), it warns if the bandwidth exceeds the rate.
+    pub fn set_sample_rate_checked(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: f64,
+        tolerance: f64,
+    ) -> Result<f64, Error> {
+        self.dev.set_sample_rate(direction, channel, rate)?;
+        self.check_bandwidth(direction, channel);
+        check_tolerance(rate, self.dev.sample_rate(direction, channel)?, tolerance)
     }
 
     /// Get the range of possible baseband sample rates.
@@ -1101,4 +2592,520 @@ impl<
     ) -> Result<Range, Error> {
         self.dev.get_sample_rate_range(direction, channel)
     }
+
+    /// Get the baseband filter bandwidth of the chain in Hz.
+    pub fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.dev.bandwidth(direction, channel)
+    }
+
+    /// Set the baseband filter bandwidth of the chain in Hz.
+    ///
+    /// Logs a warning if the bandwidth exceeds the sample rate, which causes aliasing.
+    pub fn set_bandwidth(
+        &self,
+        direction: Direction,
+        channel: usize,
+        bw: f64,
+    ) -> Result<(), Error> {
+        self.dev.set_bandwidth(direction, channel, bw)?;
+        self.check_bandwidth(direction, channel);
+        Ok(())
+    }
+
+    /// Warn if the bandwidth exceeds the sample rate, ignoring drivers that report neither.
+    fn check_bandwidth(&self, direction: Direction, channel: usize) {
+        if let (Ok(bw), Ok(rate)) = (
+            self.dev.bandwidth(direction, channel),
+            self.dev.sample_rate(direction, channel),
+        ) {
+            if bw > rate {
+                log::warn!(
+                    "bandwidth {bw} Hz exceeds sample rate {rate} of {direction} channel {channel}, signals will alias"
+                );
+            }
+        }
+    }
+
+    /// Get the range of possible baseband filter bandwidths.
+    pub fn get_bandwidth_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Range, Error> {
+        self.dev.get_bandwidth_range(direction, channel)
+    }
+
+    /// Set the baseband filter bandwidth to `fraction` of the current sample rate, clamped to the
+    /// [bandwidth range](Self::get_bandwidth_range).
+    ///
+    /// The fraction defaults to 0.75. Call this again after changing the sample rate.
+    pub fn set_bandwidth_auto(
+        &self,
+        direction: Direction,
+        channel: usize,
+        fraction: Option<f64>,
+    ) -> Result<(), Error> {
+        self.dev.set_bandwidth_auto(direction, channel, fraction)
+    }
+
+    /// Apply all parameters that are set in `config` in one call.
+    ///
+    /// Parameters are applied in the order sample rate, bandwidth, frequency, and gain. If a
+    /// parameter fails, [`Error::Apply`] reports its name together with the cause. Logs a warning
+    /// if the resulting bandwidth exceeds the sample rate.
+    pub fn apply_config(
+        &self,
+        direction: Direction,
+        channel: usize,
+        config: &DeviceConfig,
+    ) -> Result<(), Error> {
+        self.dev.apply_config(direction, channel, config)?;
+        if config.sample_rate.is_some() || config.bandwidth.is_some() {
+            self.check_bandwidth(direction, channel);
+        }
+        Ok(())
+    }
+
+    //================================ SENSORS ============================================
+
+    /// List of available sensors, e.g., temperatures or lock indicators.
+    pub fn sensors(&self) -> Result<Vec<String>, Error> {
+        self.dev.sensors()
+    }
+
+    /// Read the value of the sensor `key` as string.
+    pub fn read_sensor(&self, key: &str) -> Result<String, Error> {
+        self.dev.read_sensor(key)
+    }
+
+    /// Read the value of the sensor `key`, parsed as floating point number.
+    pub fn read_sensor_f64(&self, key: &str) -> Result<f64, Error> {
+        self.dev.read_sensor_f64(key)
+    }
+
+    //================================ CLOCKING ==============================================
+
+    /// Get the current trim value of the reference oscillator, e.g., a VCTCXO trim DAC.
+    pub fn ref_clock_tune(&self) -> Result<u16, Error> {
+        self.dev.ref_clock_tune()
+    }
+
+    /// Set the trim value of the reference oscillator, e.g., a VCTCXO trim DAC.
+    ///
+    /// This can be used to store and restore a calibrated trim value, for example, in
+    /// GPS-disciplined setups.
+    pub fn set_ref_clock_tune(&self, trim: u16) -> Result<(), Error> {
+        self.dev.set_ref_clock_tune(trim)
+    }
+
+    /// Get the [`Range`] of valid reference oscillator trim values.
+    pub fn ref_clock_tune_range(&self) -> Result<Range, Error> {
+        self.dev.ref_clock_tune_range()
+    }
+
+    /// List of available reference clock sources, e.g., `"internal"` or `"external"`.
+    pub fn clock_sources(&self) -> Result<Vec<String>, Error> {
+        self.dev.clock_sources()
+    }
+
+    /// Currently used reference clock source.
+    pub fn clock_source(&self) -> Result<String, Error> {
+        self.dev.clock_source()
+    }
+
+    /// Set the reference clock source.
+    ///
+    /// Returns [`Error::ValueError`] if `source` is not one of the [`clock_sources`](Self::clock_sources).
+    pub fn set_clock_source(&self, source: &str) -> Result<(), Error> {
+        self.dev.set_clock_source(source)
+    }
+
+    //================================ TIME ==================================================
+
+    /// Get the current hardware time of the device in nanoseconds.
+    ///
+    /// The hardware time is derived from the sample counter of the device, i.e., its effective
+    /// resolution is one sample period (`1e9 / sample_rate` ns), even though it is reported with
+    /// nanosecond granularity. Timed streamer operations, like
+    /// [`RxStreamer::activate_at`](crate::RxStreamer::activate_at), are scheduled on this clock.
+    pub fn get_hardware_time(&self) -> Result<i64, Error> {
+        self.dev.get_hardware_time()
+    }
+
+    /// Set the hardware time of the device in nanoseconds.
+    ///
+    /// See [`get_hardware_time`](Self::get_hardware_time) for the relationship to the sample rate.
+    pub fn set_hardware_time(&self, time_ns: i64) -> Result<(), Error> {
+        self.dev.set_hardware_time(time_ns)
+    }
+
+    //================================ STREAM FORMAT =========================================
+
+    /// Sample formats that can be requested for streams through the `stream_format` argument.
+    ///
+    /// [`SampleFormat::CF32`] is always supported.
+    pub fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        self.dev.supported_formats(direction)
+    }
+
+    //================================ GPIO ==================================================
+
+    /// List available GPIO banks, e.g., the pins of expansion headers.
+    pub fn gpio_banks(&self) -> Result<Vec<String>, Error> {
+        self.dev.gpio_banks()
+    }
+
+    /// Read the value of the pins of a GPIO `bank`.
+    ///
+    /// Fails with [`Error::ValueError`] if `bank` is not one of the [`gpio_banks`](Device::gpio_banks).
+    pub fn read_gpio(&self, bank: &str) -> Result<u32, Error> {
+        self.dev.read_gpio(bank)
+    }
+
+    /// Write `value` to the pins of a GPIO `bank`, only changing the pins that are set in `mask`.
+    ///
+    /// Fails with [`Error::ValueError`] if `bank` is not one of the [`gpio_banks`](Device::gpio_banks).
+    pub fn write_gpio(&self, bank: &str, value: u32, mask: u32) -> Result<(), Error> {
+        self.dev.write_gpio(bank, value, mask)
+    }
+
+    //================================ LOOPBACK ==============================================
+
+    /// List available loopback modes, e.g., `"BB"` or `"RF"`, for testing without antennas.
+    pub fn loopback_modes(&self) -> Result<Vec<String>, Error> {
+        self.dev.loopback_modes()
+    }
+
+    /// Set the loopback mode.
+    ///
+    /// `"NONE"` disables loopback. Fails with [`Error::ValueError`] for modes not listed in
+    /// [`loopback_modes`](Device::loopback_modes).
+    pub fn set_loopback(&self, mode: &str) -> Result<(), Error> {
+        self.dev.set_loopback(mode)
+    }
+
+    /// Get the current loopback mode.
+    pub fn loopback(&self) -> Result<String, Error> {
+        self.dev.loopback()
+    }
+
+    //================================ CALIBRATION ===========================================
+
+    /// Export the current calibration results in the binary format of [`Calibration`](crate::Calibration).
+    ///
+    /// Can be restored with [`import_calibration`](Device::import_calibration), e.g., to avoid
+    /// calibrating the device each time it is opened.
+    pub fn export_calibration(&self) -> Result<Vec<u8>, Error> {
+        self.dev.export_calibration()
+    }
+
+    /// Restore calibration results, exported with [`export_calibration`](Device::export_calibration).
+    pub fn import_calibration(&self, data: &[u8]) -> Result<(), Error> {
+        self.dev.import_calibration(data)
+    }
+
+    //================================ CAPABILITIES ==========================================
+
+    /// Snapshot of the capabilities of the device, e.g., to dump them as JSON.
+    pub fn capabilities(&self) -> Result<Capabilities, Error> {
+        self.dev.capabilities()
+    }
+
+    //================================ FPGA ==================================================
+
+    /// Load an FPGA image at runtime.
+    ///
+    /// Fails with [`Error::ValueError`] if the size of the `bitstream` does not fit the device.
+    pub fn load_fpga(&self, bitstream: &[u8]) -> Result<(), Error> {
+        self.dev.load_fpga(bitstream)
+    }
+
+    /// Whether an FPGA image is loaded.
+    pub fn fpga_loaded(&self) -> Result<bool, Error> {
+        self.dev.fpga_loaded()
+    }
+}
+
+/// Split `total` across gain stages with the given `ranges`, filling earlier stages first.
+///
+/// Returns `None` if one of the ranges is empty.
+pub(crate) fn distribute_gain(ranges: &[Range], total: f64) -> Option<Vec<f64>> {
+    let mins = ranges.iter().map(|r| r.min()).collect::<Option<Vec<_>>>()?;
+    let mut extra = total - mins.iter().sum::<f64>();
+    let mut gains = Vec::with_capacity(ranges.len());
+
+    for (r, min) in ranges.iter().zip(mins) {
+        let max = r.max()?;
+        let g = r.at_max(min + extra.clamp(0.0, max - min)).unwrap_or(min);
+        extra -= g - min;
+        gains.push(g);
+    }
+    Some(gains)
+}
+
+/// Deferred call to a driver's `open`.
+pub(crate) type Opener<'a, T> = Box<dyn FnOnce() -> Result<T, Error> + 'a>;
+
+/// Call the `openers` in order and return the first device that could be opened.
+///
+/// If `explicit`, a driver was requested explicitly and its error is returned unchanged.
+/// Otherwise, the errors of all drivers are aggregated.
+pub(crate) fn open_first<T>(
+    openers: Vec<(Driver, Opener<'_, T>)>,
+    explicit: bool,
+) -> Result<T, Error> {
+    let mut errors = Vec::new();
+    for (driver, open) in openers {
+        match open() {
+            Ok(d) => return Ok(d),
+            Err(e) if explicit => return Err(e),
+            Err(e) => errors.push((driver, e)),
+        }
+    }
+    if errors.iter().all(|(_, e)| matches!(e, Error::NotFound)) {
+        Err(Error::NotFound)
+    } else {
+        Err(Error::OpenFailed(errors))
+    }
+}
+
+/// Check `gain` against `range`, the result of a gain range query.
+///
+/// Out-of-range gains are clamped to the closest valid gain with a warning if `clamp` is set and
+/// fail with [`Error::OutOfRange`] otherwise. Without a range, i.e., [`Error::NotSupported`], any
+/// gain but NaN is accepted.
+pub(crate) fn check_gain(
+    range: Result<Range, Error>,
+    gain: f64,
+    clamp: bool,
+) -> Result<f64, Error> {
+    if gain.is_nan() {
+        return Err(Error::ValueError);
+    }
+    match range {
+        Ok(r) if !r.contains(gain) => match r.closest(gain) {
+            Some(g) if clamp => {
+                log::warn!("gain {gain} dB out of range, clamping to {g} dB");
+                Ok(g)
+            }
+            _ => Err(Error::OutOfRange(r, gain)),
+        },
+        Ok(_) | Err(Error::NotSupported) => Ok(gain),
+        Err(e) => Err(e),
+    }
+}
+
+/// Get the `stream_format` from the streamer `args`, checking that it is `supported`.
+///
+/// Returns [`SampleFormat::CF32`] if no format is requested.
+pub(crate) fn stream_format(
+    args: &Args,
+    supported: &[SampleFormat],
+) -> Result<SampleFormat, Error> {
+    let format = match args.get::<SampleFormat>("stream_format") {
+        Ok(f) => f,
+        Err(Error::NotFound) => SampleFormat::CF32,
+        Err(e) => return Err(e),
+    };
+    if format == SampleFormat::CF32 || supported.contains(&format) {
+        Ok(format)
+    } else {
+        Err(Error::ValueError)
+    }
+}
+
+/// Check that `channels` is a non-empty list of distinct channel indices below `num_channels`.
+pub(crate) fn check_channels(channels: &[usize], num_channels: usize) -> Result<(), Error> {
+    if channels.is_empty() {
+        return Err(Error::ValueError);
+    }
+    for (i, c) in channels.iter().enumerate() {
+        if *c >= num_channels {
+            return Err(Error::InvalidChannel(*c, (0..num_channels).collect()));
+        }
+        if channels[..i].contains(c) {
+            return Err(Error::ValueError);
+        }
+    }
+    Ok(())
+}
+
+/// Default fraction of the sample rate used by [`DeviceTrait::set_bandwidth_auto`].
+const BANDWIDTH_FRACTION: f64 = 0.75;
+
+/// Round `value` in Hz to the nearest integer for drivers that only take integer rates or
+/// frequencies, rejecting NaN, negative values, and values above `max`.
+pub(crate) fn round_hz(value: f64, max: u64) -> Result<u64, Error> {
+    let rounded = value.round();
+    if !(0.0..=max as f64).contains(&rounded) {
+        return Err(Error::ValueError);
+    }
+    if rounded != value {
+        log::debug!("rounding {value} Hz to {rounded} Hz");
+    }
+    Ok(rounded as u64)
+}
+
+/// Marks the streamer of a direction/channel as in use, until it is dropped.
+pub(crate) struct StreamerGuard {
+    busy: Arc<AtomicBool>,
+}
+
+impl StreamerGuard {
+    /// Claim `busy`, failing if another streamer already holds it.
+    pub(crate) fn acquire(busy: &Arc<AtomicBool>) -> Result<Self, Error> {
+        busy.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .or(Err(Error::Busy))?;
+        Ok(Self { busy: busy.clone() })
+    }
+}
+
+impl Drop for StreamerGuard {
+    fn drop(&mut self) {
+        self.busy.store(false, Ordering::Release);
+    }
+}
+
+/// Check that the `actual` value is within `tolerance` of the `requested` one.
+pub(crate) fn check_tolerance(requested: f64, actual: f64, tolerance: f64) -> Result<f64, Error> {
+    if (actual - requested).abs() <= tolerance {
+        Ok(actual)
+    } else {
+        Err(Error::OutOfRange(
+            Range::new(vec![RangeItem::Interval(
+                requested - tolerance,
+                requested + tolerance,
+            )]),
+            actual,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_distribution() {
+        let ranges = [
+            Range::new(vec![RangeItem::Value(0.0), RangeItem::Value(6.0)]),
+            Range::new(vec![RangeItem::Interval(5.0, 30.0)]),
+            Range::new(vec![RangeItem::Step(0.0, 30.0, 3.0)]),
+        ];
+        assert_eq!(distribute_gain(&ranges, 0.0).unwrap(), vec![0.0, 5.0, 0.0]);
+        assert_eq!(
+            distribute_gain(&ranges, 20.0).unwrap(),
+            vec![6.0, 14.0, 0.0]
+        );
+        assert_eq!(
+            distribute_gain(&ranges, 45.0).unwrap(),
+            vec![6.0, 30.0, 9.0]
+        );
+        assert_eq!(
+            distribute_gain(&ranges, 47.0).unwrap(),
+            vec![6.0, 30.0, 9.0]
+        );
+        assert_eq!(
+            distribute_gain(&ranges, 100.0).unwrap(),
+            vec![6.0, 30.0, 30.0]
+        );
+        assert!(distribute_gain(&[Range::new(Vec::new())], 10.0).is_none());
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(round_hz(3.2e6, u32::MAX as u64).unwrap(), 3_200_000);
+        assert_eq!(round_hz(2_304_000.5, u32::MAX as u64).unwrap(), 2_304_001);
+        assert_eq!(round_hz(2_304_000.4, u32::MAX as u64).unwrap(), 2_304_000);
+        assert_eq!(
+            round_hz(u32::MAX as f64, u32::MAX as u64).unwrap(),
+            u32::MAX as u64
+        );
+        assert!(matches!(
+            round_hz(u32::MAX as f64 + 1.0, u32::MAX as u64),
+            Err(Error::ValueError)
+        ));
+        for v in [f64::NAN, -1.0, f64::INFINITY] {
+            assert!(matches!(round_hz(v, u64::MAX), Err(Error::ValueError)));
+        }
+    }
+
+    #[test]
+    fn open_first_success() {
+        let openers: Vec<(Driver, Opener<u32>)> = vec![
+            (Driver::Aaronia, Box::new(|| Err(Error::NotFound))),
+            (Driver::RtlSdr, Box::new(|| Err(Error::DeviceError))),
+            (Driver::Soapy, Box::new(|| Ok(3))),
+        ];
+        assert_eq!(open_first(openers, false).unwrap(), 3);
+    }
+
+    #[test]
+    fn open_first_aggregate() {
+        let openers: Vec<(Driver, Opener<u32>)> = vec![
+            (Driver::Aaronia, Box::new(|| Err(Error::NotFound))),
+            (Driver::RtlSdr, Box::new(|| Err(Error::DeviceError))),
+        ];
+        match open_first(openers, false) {
+            Err(e @ Error::OpenFailed(_)) => {
+                let Error::OpenFailed(errors) = &e else {
+                    unreachable!()
+                };
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[1].0, Driver::RtlSdr);
+                assert!(matches!(errors[1].1, Error::DeviceError));
+                assert_eq!(
+                    e.to_string(),
+                    "No driver could open the device (Aaronia: Not Found, RtlSdr: DeviceError)"
+                );
+            }
+            r => panic!("unexpected result {:?}", r.map(|_| ())),
+        }
+
+        let openers: Vec<(Driver, Opener<u32>)> =
+            vec![(Driver::Aaronia, Box::new(|| Err(Error::NotFound)))];
+        assert!(matches!(open_first(openers, false), Err(Error::NotFound)));
+        let openers: Vec<(Driver, Opener<u32>)> =
+            vec![(Driver::RtlSdr, Box::new(|| Err(Error::DeviceError)))];
+        assert!(matches!(open_first(openers, true), Err(Error::DeviceError)));
+    }
+
+    #[test]
+    fn channels_valid() {
+        assert!(check_channels(&[0], 1).is_ok());
+        assert!(check_channels(&[1, 0], 2).is_ok());
+    }
+
+    #[test]
+    fn channels_invalid() {
+        assert!(matches!(check_channels(&[], 1), Err(Error::ValueError)));
+        assert!(matches!(check_channels(&[0, 0], 2), Err(Error::ValueError)));
+        match check_channels(&[0, 1], 1) {
+            Err(e @ Error::InvalidChannel(1, _)) => {
+                assert_eq!(e.to_string(), "Invalid channel 1, valid channels: [0]")
+            }
+            r => panic!("unexpected result {r:?}"),
+        }
+    }
+
+    #[test]
+    fn tolerance() {
+        assert_eq!(
+            check_tolerance(100e6, 100e6 + 5.0, 10.0).unwrap(),
+            100e6 + 5.0
+        );
+        assert!(matches!(
+            check_tolerance(100e6, 100e6 - 20.0, 10.0),
+            Err(Error::OutOfRange(_, a)) if a == 100e6 - 20.0
+        ));
+    }
+
+    #[test]
+    fn streamer_guard() {
+        let busy = Arc::new(AtomicBool::new(false));
+        let g = StreamerGuard::acquire(&busy).unwrap();
+        assert!(matches!(StreamerGuard::acquire(&busy), Err(Error::Busy)));
+        drop(g);
+        assert!(StreamerGuard::acquire(&busy).is_ok());
+    }
 }
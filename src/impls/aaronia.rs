@@ -12,6 +12,7 @@ use aaronia_rtsa::Device as Sdr;
 use aaronia_rtsa::DeviceInfo;
 use aaronia_rtsa::Packet;
 
+use crate::logging::LogContext;
 use crate::Args;
 use crate::DeviceTrait;
 use crate::Direction;
@@ -32,16 +33,39 @@ unsafe impl Send for Aaronia {}
 unsafe impl Sync for Aaronia {}
 
 /// Aaronia SpectranV6 RX Streamer
+///
+/// Dropping an active streamer stops the device. Prefer an explicit
+/// [`deactivate_at`](crate::RxStreamer::deactivate_at) for timing control.
 pub struct RxStreamer {
     dev: Arc<Mutex<Sdr>>,
     packet: Option<(Packet, usize)>,
+    active: bool,
+    log: LogContext,
 }
 
 unsafe impl Send for RxStreamer {}
 
 impl RxStreamer {
-    fn new(dev: Arc<Mutex<Sdr>>) -> Self {
-        Self { dev, packet: None }
+    fn new(dev: Arc<Mutex<Sdr>>, log: LogContext) -> Self {
+        Self {
+            dev,
+            packet: None,
+            active: false,
+            log,
+        }
+    }
+}
+
+impl Drop for RxStreamer {
+    fn drop(&mut self) {
+        if self.active {
+            if let Err(e) = crate::RxStreamer::deactivate(self) {
+                log::warn!(
+                    "{}: failed to deactivate dropped RX streamer ({e})",
+                    self.log.channel(Rx, 0)
+                );
+            }
+        }
     }
 }
 
@@ -71,7 +95,11 @@ impl Aaronia {
         Ok(devs
             .iter()
             .enumerate()
-            .map(|(i, d)| format!("index={i}, driver=aaronia").parse().unwrap())
+            .map(|(i, d)| {
+                format!("index={i}, driver={}", Driver::Aaronia)
+                    .parse()
+                    .unwrap()
+            })
             .collect())
     }
 
@@ -126,7 +154,7 @@ impl DeviceTrait for Aaronia {
     }
 
     fn info(&self) -> Result<crate::Args, Error> {
-        format!("driver=aaronia, index={}", self.index).try_into()
+        format!("driver={}, index={}", Driver::Aaronia, self.index).try_into()
     }
 
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
@@ -149,10 +177,16 @@ impl DeviceTrait for Aaronia {
         channels: &[usize],
         args: crate::Args,
     ) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        // streaming is only implemented for the first channel
         if channels == [0] {
-            Ok(RxStreamer::new(self.dev.clone()))
+            Ok(RxStreamer::new(
+                self.dev.clone(),
+                LogContext::new(Driver::Aaronia, "index", self.index.to_string()),
+            ))
         } else {
-            Err(Error::ValueError)
+            log::warn!("aaronia: streaming is only implemented for channel 0, not {channels:?}");
+            Err(Error::NotSupported)
         }
     }
 
@@ -161,10 +195,13 @@ impl DeviceTrait for Aaronia {
         channels: &[usize],
         args: crate::Args,
     ) -> Result<Self::TxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Tx)?)?;
+        // streaming is only implemented for the first channel
         if channels == [0] {
             Ok(TxStreamer::new(self.dev.clone()))
         } else {
-            Err(Error::ValueError)
+            log::warn!("aaronia: streaming is only implemented for channel 0, not {channels:?}");
+            Err(Error::NotSupported)
         }
     }
 
@@ -206,6 +243,7 @@ impl DeviceTrait for Aaronia {
     fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
         match (direction, channel) {
             (Rx, 0 | 1) => Ok(true),
+            (Tx, 0) => Ok(false),
             _ => Err(Error::ValueError),
         }
     }
@@ -279,7 +317,7 @@ impl DeviceTrait for Aaronia {
         match (direction, channel) {
             (Rx, 0) => Ok(None),
             (Rx, 1) => Ok(None),
-            (Tx, 0) => todo!(),
+            (Tx, 0) => Err(Error::NotSupported),
             _ => Err(Error::ValueError),
         }
     }
@@ -431,7 +469,7 @@ impl DeviceTrait for Aaronia {
                 }
                 Err(Error::ValueError)
             }
-            (Tx, 0) => todo!(),
+            (Tx, 0) => Err(Error::NotSupported),
             _ => Err(Error::ValueError),
         }
     }
@@ -444,7 +482,7 @@ impl DeviceTrait for Aaronia {
                     .map(|v| RangeItem::Value(92e6 / v))
                     .collect(),
             )),
-            (Tx, 0) => todo!(),
+            (Tx, 0) => Err(Error::NotSupported),
             _ => Err(Error::ValueError),
         }
     }
@@ -456,13 +494,22 @@ impl crate::RxStreamer for RxStreamer {
     }
 
     fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        if self.active {
+            return Ok(());
+        }
         let mut dev = self.dev.lock().unwrap();
         dev.connect().or(Err(Error::DeviceError))?;
-        dev.start().or(Err(Error::DeviceError))
+        dev.start().or(Err(Error::DeviceError))?;
+        self.active = true;
+        Ok(())
     }
 
     fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        if !self.active {
+            return Ok(());
+        }
         let mut dev = self.dev.lock().unwrap();
+        self.active = false;
         dev.stop().or(Err(Error::DeviceError))?;
         dev.disconnect().or(Err(Error::DeviceError))
     }
@@ -476,7 +523,7 @@ impl crate::RxStreamer for RxStreamer {
         debug_assert_eq!(buffers.len(), 1);
 
         let mut i = 0;
-        let len = buffers[0].len();
+        let len = std::cmp::min(buffers[0].len(), self.mtu()?);
         while i < len {
             match self.packet.take() {
                 None => {
@@ -514,31 +561,31 @@ impl crate::TxStreamer for TxStreamer {
         Ok(1024)
     }
 
-    fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        todo!()
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Err(Error::NotSupported)
     }
 
-    fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        todo!()
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Err(Error::NotSupported)
     }
 
     fn write(
         &mut self,
-        buffers: &[&[num_complex::Complex32]],
-        at_ns: Option<i64>,
-        end_burst: bool,
-        timeout_us: i64,
+        _buffers: &[&[num_complex::Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
     ) -> Result<usize, Error> {
-        todo!()
+        Err(Error::NotSupported)
     }
 
     fn write_all(
         &mut self,
-        buffers: &[&[num_complex::Complex32]],
-        at_ns: Option<i64>,
-        end_burst: bool,
-        timeout_us: i64,
+        _buffers: &[&[num_complex::Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
     ) -> Result<(), Error> {
-        todo!()
+        Err(Error::NotSupported)
     }
 }
@@ -3,14 +3,19 @@ use num_complex::Complex32;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 use std::time::SystemTime;
 use ureq::serde_json::json;
 use ureq::serde_json::Value;
 use ureq::Agent;
 
+use crate::device::StreamerGuard;
+use crate::logging::LogContext;
 use crate::Args;
 use crate::DeviceTrait;
 use crate::Direction;
@@ -20,7 +25,15 @@ use crate::Error;
 use crate::Range;
 use crate::RangeItem;
 
+/// Gain elements with their direction and range in dB.
+const GAIN_ELEMENTS: [(Direction, &str, f64, f64); 2] =
+    [(Rx, "TUNER", 0.0, 30.0), (Tx, "TUNER", -100.0, 10.0)];
+
 /// Aaronia SpectranV6 driver, using the HTTP interface
+///
+/// RX tuning has two frequency components: the `RF` center frequency of the receiver and the
+/// `DEMOD` offset of the IQ demodulator relative to it. The frequency of the channel is their
+/// sum. TX only has an `RF` component.
 #[derive(Clone)]
 pub struct AaroniaHttp {
     url: String,
@@ -29,26 +42,45 @@ pub struct AaroniaHttp {
     f_offset: f64,
     tx_frequency: Arc<AtomicU64>,
     tx_sample_rate: Arc<AtomicU64>,
+    rx_busy: Arc<AtomicBool>,
+    tx_busy: Arc<AtomicBool>,
+    log: LogContext,
 }
 
 /// Aaronia SpectranV6 HTTP RX Streamer
+///
+/// Only one RX streamer can exist at a time.
 pub struct RxStreamer {
     agent: Agent,
     url: String,
     items_left: usize,
     reader: Option<BufReader<Box<dyn Read + Send + Sync + 'static>>>,
+    _guard: StreamerGuard,
 }
 
 /// expected maximum delay for the transfer of samples between host and rf hardware, used to set the transmit start time to an achievalble but close value; in seconds
 const STREAMING_DELAY: f64 = 0.01; // 0.2 is too much, 0.001 too little
+/// maximum number of samples returned by a single read
+const RX_MTU: usize = 65536;
+/// interval at which a blocked write re-checks for free space in the tx queue
+const TX_POLL_INTERVAL: Duration = Duration::from_millis(1);
+/// time that deactivating a streamer waits for the tx queue to drain, covering a full queue
+const TX_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Aaronia SpectranV6 HTTP TX Streamer
+///
+/// Samples are queued for up to one second on the server. If the `drain` argument is set when
+/// creating the streamer, deactivating it waits for the queue to [drain](crate::TxStreamer::drain).
+///
+/// Only one TX streamer can exist at a time.
 pub struct TxStreamer {
     agent: Agent,
     url: String,
     frequency: Arc<AtomicU64>,
     sample_rate: Arc<AtomicU64>,
     last_transmission_end_time: f64,
+    drain_on_deactivate: bool,
+    _guard: StreamerGuard,
 }
 
 impl AaroniaHttp {
@@ -76,7 +108,7 @@ impl AaroniaHttp {
         };
         if resp.status() == 200 {
             let mut args = args.clone();
-            args.merge(format!("driver=aaronia_http, url={url}").try_into()?);
+            args.merge(format!("driver={}, url={url}", Driver::AaroniaHttp).try_into()?);
             Ok(vec![args])
         } else {
             Ok(Vec::new())
@@ -99,11 +131,14 @@ impl AaroniaHttp {
 
             Ok(Self {
                 agent: Agent::new(),
+                log: LogContext::new(Driver::AaroniaHttp, "url", url.clone()),
                 url,
                 tx_url,
                 f_offset,
                 tx_frequency: Arc::new(AtomicU64::new(2_450_000_000)),
                 tx_sample_rate: Arc::new(AtomicU64::new(1_000_000)),
+                rx_busy: Arc::new(AtomicBool::new(false)),
+                tx_busy: Arc::new(AtomicBool::new(false)),
             })
         }
     }
@@ -166,11 +201,11 @@ impl DeviceTrait for AaroniaHttp {
     }
 
     fn id(&self) -> Result<String, Error> {
-        Ok(format!("driver=aarnia_http, url={}", self.url))
+        Ok(format!("driver={}, url={}", Driver::AaroniaHttp, self.url))
     }
 
     fn info(&self) -> Result<Args, Error> {
-        format!("driver=aarnia_http, url={}", self.url).try_into()
+        format!("driver={}, url={}", Driver::AaroniaHttp, self.url).try_into()
     }
 
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
@@ -188,21 +223,37 @@ impl DeviceTrait for AaroniaHttp {
         }
     }
 
-    fn rx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::RxStreamer, Error> {
+    fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        crate::device::stream_format(&args, &self.supported_formats(Rx)?)?;
+        // streaming is only implemented for the first channel
         if channels == [0] {
             Ok(RxStreamer {
                 url: self.url.clone(),
                 agent: self.agent.clone(),
                 items_left: 0,
                 reader: None,
+                _guard: StreamerGuard::acquire(&self.rx_busy)?,
             })
         } else {
-            Err(Error::ValueError)
+            log::warn!(
+                "{}: streaming is only implemented for channel 0, not {channels:?}",
+                self.log
+            );
+            Err(Error::NotSupported)
         }
     }
 
-    fn tx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::TxStreamer, Error> {
+    fn tx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::TxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Tx)?)?;
+        crate::device::stream_format(&args, &self.supported_formats(Tx)?)?;
+        // streaming is only implemented for the first channel
         if channels == [0] {
+            let drain_on_deactivate = match args.get::<bool>("drain") {
+                Ok(d) => d,
+                Err(Error::NotFound) => false,
+                Err(e) => return Err(e),
+            };
             Ok(TxStreamer {
                 url: self.tx_url.clone(),
                 agent: self.agent.clone(),
@@ -212,9 +263,15 @@ impl DeviceTrait for AaroniaHttp {
                     .duration_since(SystemTime::UNIX_EPOCH)
                     .unwrap()
                     .as_secs_f64(),
+                drain_on_deactivate,
+                _guard: StreamerGuard::acquire(&self.tx_busy)?,
             })
         } else {
-            Err(Error::ValueError)
+            log::warn!(
+                "{}: streaming is only implemented for channel 0, not {channels:?}",
+                self.log
+            );
+            Err(Error::NotSupported)
         }
     }
 
@@ -247,8 +304,11 @@ impl DeviceTrait for AaroniaHttp {
 
     fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         match (direction, channel) {
-            (Rx, 0 | 1) => Ok(vec!["TUNER".to_string()]),
-            (Tx, 0) => Ok(vec!["TUNER".to_string()]),
+            (Rx, 0 | 1) | (Tx, 0) => Ok(GAIN_ELEMENTS
+                .iter()
+                .filter(|e| e.0 == direction)
+                .map(|e| e.1.to_string())
+                .collect()),
             _ => Err(Error::ValueError),
         }
     }
@@ -256,6 +316,7 @@ impl DeviceTrait for AaroniaHttp {
     fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
         match (direction, channel) {
             (Rx, 0 | 1) => Ok(true),
+            (Tx, 0) => Ok(false),
             _ => Err(Error::ValueError),
         }
     }
@@ -277,17 +338,18 @@ impl DeviceTrait for AaroniaHttp {
         }
     }
 
-    fn agc(&self, _direction: Direction, _channel: usize) -> Result<bool, Error> {
-        let (_, s) = self.get_enum(vec![
-            "Block_Spectran_V6B_0",
-            "config",
-            "device",
-            "gaincontrol",
-        ])?;
-        if s == "manual" {
-            Ok(false)
-        } else {
-            Ok(true)
+    fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        match (direction, channel) {
+            (Rx, 0 | 1) => {
+                let (_, s) = self.get_enum(vec![
+                    "Block_Spectran_V6B_0",
+                    "config",
+                    "device",
+                    "gaincontrol",
+                ])?;
+                Ok(gaincontrol_is_agc(&s))
+            }
+            _ => Err(Error::ValueError),
         }
     }
 
@@ -306,9 +368,9 @@ impl DeviceTrait for AaroniaHttp {
                 self.send_json(json)
             }
             (Tx, 0) => {
-                let range = Range::new(vec![RangeItem::Interval(-100.0, 10.0)]);
+                let range = self.gain_range(Tx, 0)?;
                 if !range.contains(gain) {
-                    log::warn!("aaronia_http: gain out of range");
+                    log::warn!("{}: gain out of range", self.log.channel(Tx, 0));
                     return Err(Error::OutOfRange(range, gain));
                 }
                 let json = json!({
@@ -332,49 +394,66 @@ impl DeviceTrait for AaroniaHttp {
                     self.get_f64(vec!["Block_Spectran_V6B_0", "config", "main", "reflevel"])?;
                 Ok(Some(-lvl - 8.0))
             }
-            _ => {
-                todo!()
+            (Tx, 0) => {
+                let attn =
+                    self.get_f64(vec!["Block_Spectran_V6B_0", "config", "main", "transattn"])?;
+                Ok(Some(attn))
             }
+            _ => Err(Error::ValueError),
         }
     }
 
     fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
-        match (direction, channel) {
-            (Rx, 0 | 1) => Ok(Range::new(vec![RangeItem::Interval(0.0, 30.0)])),
-            _ => todo!(),
-        }
+        self.gain_element_range(direction, channel, "TUNER")
     }
 
     fn set_gain_element(
         &self,
-        _direction: Direction,
-        _channel: usize,
-        _name: &str,
-        _gain: f64,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        gain: f64,
     ) -> Result<(), Error> {
-        todo!()
+        // the TUNER is the only element, validated through its range
+        self.gain_element_range(direction, channel, name)?;
+        self.set_gain(direction, channel, gain)
     }
 
     fn gain_element(
         &self,
-        _direction: Direction,
-        _channel: usize,
-        _name: &str,
+        direction: Direction,
+        channel: usize,
+        name: &str,
     ) -> Result<Option<f64>, Error> {
-        todo!()
+        self.gain_element_range(direction, channel, name)?;
+        self.gain(direction, channel)
     }
 
     fn gain_element_range(
         &self,
-        _direction: Direction,
-        _channel: usize,
-        _name: &str,
+        direction: Direction,
+        channel: usize,
+        name: &str,
     ) -> Result<Range, Error> {
-        todo!()
+        let elements = self.gain_elements(direction, channel)?;
+        match GAIN_ELEMENTS
+            .iter()
+            .find(|e| e.0 == direction && e.1 == name)
+        {
+            Some(&(_, _, min, max)) => Ok(Range::new(vec![RangeItem::Interval(min, max)])),
+            None => {
+                log::warn!(
+                    "{}: unknown gain element {name}, valid elements are {}",
+                    self.log.channel(direction, channel),
+                    elements.join(", ")
+                );
+                Err(Error::ValueError)
+            }
+        }
     }
 
-    fn frequency_range(&self, _direction: Direction, _channel: usize) -> Result<Range, Error> {
-        todo!()
+    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.component_frequency_range(direction, channel, "RF")
     }
 
     fn frequency(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
@@ -395,13 +474,17 @@ impl DeviceTrait for AaroniaHttp {
         direction: Direction,
         channel: usize,
         frequency: f64,
-        _args: Args,
+        args: Args,
     ) -> Result<(), Error> {
         match (direction, channel) {
             (Rx, 0 | 1) => {
-                let f = (frequency - self.f_offset).max(0.0);
-                self.set_component_frequency(direction, channel, "RF", f)?;
-                self.set_component_frequency(direction, channel, "DEMOD", self.f_offset)
+                let offset = match args.get::<f64>("OFFSET") {
+                    Ok(o) => o,
+                    Err(Error::NotFound) => self.f_offset,
+                    Err(e) => return Err(e),
+                };
+                let offset = offset.min(frequency);
+                self.tune(direction, channel, frequency, offset)
             }
             (Tx, 0) => self.set_component_frequency(direction, channel, "RF", frequency),
             _ => Err(Error::ValueError),
@@ -415,17 +498,25 @@ impl DeviceTrait for AaroniaHttp {
     ) -> Result<Vec<String>, Error> {
         match (direction, channel) {
             (Rx, 0 | 1) => Ok(vec!["RF".to_string(), "DEMOD".to_string()]),
-            _ => todo!(),
+            (Tx, 0) => Ok(vec!["RF".to_string()]),
+            _ => Err(Error::ValueError),
         }
     }
 
     fn component_frequency_range(
         &self,
-        _direction: Direction,
-        _channel: usize,
-        _name: &str,
+        direction: Direction,
+        channel: usize,
+        name: &str,
     ) -> Result<Range, Error> {
-        todo!()
+        match (direction, channel, name) {
+            (Rx, 0 | 1, "RF") | (Tx, 0, "RF") => {
+                Ok(Range::new(vec![RangeItem::Interval(193e6, 6e9)]))
+            }
+            // offset has to stay within the maximum IQ bandwidth
+            (Rx, 0 | 1, "DEMOD") => Ok(Range::new(vec![RangeItem::Interval(-46.08e6, 46.08e6)])),
+            _ => Err(Error::ValueError),
+        }
     }
 
     fn component_frequency(
@@ -449,7 +540,8 @@ impl DeviceTrait for AaroniaHttp {
             (Rx, 0 | 1, "RF") => {
                 self.get_f64(vec!["Block_Spectran_V6B_0", "config", "main", "centerfreq"])
             }
-            _ => todo!(),
+            (Tx, 0, "RF") => Ok(self.tx_frequency.load(Ordering::SeqCst) as f64),
+            _ => Err(Error::ValueError),
         }
     }
 
@@ -486,7 +578,14 @@ impl DeviceTrait for AaroniaHttp {
                 self.send_json(json)
             }
             (Tx, 0, "RF") => {
-                self.tx_frequency.store(frequency as u64, Ordering::SeqCst);
+                if !self
+                    .component_frequency_range(Tx, 0, "RF")?
+                    .contains(frequency)
+                {
+                    return Err(Error::ValueError);
+                }
+                let frequency = crate::device::round_hz(frequency, u64::MAX)?;
+                self.tx_frequency.store(frequency, Ordering::SeqCst);
                 Ok(())
             }
             _ => Err(Error::ValueError),
@@ -526,7 +625,8 @@ impl DeviceTrait for AaroniaHttp {
                 self.send_json(json)
             }
             (Tx, 0) => {
-                self.tx_sample_rate.store(rate as u64, Ordering::SeqCst);
+                let rate = crate::device::round_hz(rate, u64::MAX)?;
+                self.tx_sample_rate.store(rate, Ordering::SeqCst);
                 Ok(())
             }
             _ => Err(Error::ValueError),
@@ -536,7 +636,7 @@ impl DeviceTrait for AaroniaHttp {
     fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
         match (direction, channel) {
             (Rx, 0 | 1) => Ok(Range::new(vec![RangeItem::Interval(0.0, 92.16e6)])),
-            (Tx, 0) => todo!(),
+            (Tx, 0) => Err(Error::NotSupported),
             _ => Err(Error::ValueError),
         }
     }
@@ -563,10 +663,13 @@ impl RxStreamer {
 
 impl crate::RxStreamer for RxStreamer {
     fn mtu(&self) -> Result<usize, Error> {
-        Ok(65536)
+        Ok(RX_MTU)
     }
 
     fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        if self.reader.is_some() {
+            return Ok(());
+        }
         let r = self
             .agent
             .get(&format!("{}/stream?format=float32", self.url))
@@ -581,6 +684,13 @@ impl crate::RxStreamer for RxStreamer {
         Ok(())
     }
 
+    fn samples_available(&self) -> Result<usize, Error> {
+        let reader = self.reader.as_ref().ok_or(Error::Inactive)?;
+        // only samples of the current block that are already buffered
+        let buffered = reader.buffer().len() / std::mem::size_of::<Complex32>();
+        Ok(std::cmp::min(self.items_left, buffered))
+    }
+
     fn read(
         &mut self,
         buffers: &mut [&mut [num_complex::Complex32]],
@@ -592,6 +702,7 @@ impl crate::RxStreamer for RxStreamer {
 
         let is = std::mem::size_of::<Complex32>();
         let n = std::cmp::min(self.items_left, buffers[0].len());
+        let n = std::cmp::min(n, RX_MTU);
 
         let out =
             unsafe { std::slice::from_raw_parts_mut(buffers[0].as_mut_ptr() as *mut u8, n * is) };
@@ -616,50 +727,83 @@ impl crate::TxStreamer for TxStreamer {
     }
 
     fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        if self.drain_on_deactivate {
+            self.drain(TX_DRAIN_TIMEOUT.as_micros() as i64)?;
+        }
         Ok(())
     }
 
+    fn drain(&mut self, timeout_us: i64) -> Result<(), Error> {
+        let deadline = Instant::now() + Duration::from_micros(timeout_us.max(0) as u64);
+        loop {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64();
+            let queued = self.last_transmission_end_time - now;
+            if queued <= 0.0 {
+                return Ok(());
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout(0));
+            }
+            std::thread::sleep(remaining.min(Duration::from_secs_f64(queued)));
+        }
+    }
+
     fn write(
         &mut self,
         buffers: &[&[num_complex::Complex32]],
         at_ns: Option<i64>,
         end_burst: bool,
-        _timeout_us: i64,
+        timeout_us: i64,
     ) -> Result<usize, Error> {
         debug_assert_eq!(buffers.len(), 1);
-        debug_assert_eq!(at_ns, None);
 
         let frequency = self.frequency.load(Ordering::SeqCst) as f64;
         let sample_rate = self.sample_rate.load(Ordering::SeqCst) as f64;
         let len: usize = buffers[0].len();
+        let deadline = Instant::now() + Duration::from_micros(timeout_us.max(0) as u64);
+        let offset = at_ns
+            .map(|t| t as f64 / 1e9)
+            .unwrap_or(0.0)
+            .max(STREAMING_DELAY);
+        // A burst is sent in one go and the TX queue holds at most one second of samples.
+        if end_burst && len as f64 > sample_rate {
+            return Err(Error::ValueError);
+        }
 
-        let start = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64()
-            + STREAMING_DELAY;
-        let num_streamable_samples = if start < self.last_transmission_end_time {
-            // log::debug!("WARNING: cannot send immediately, expecting {}s delay.", self.last_transmission_end_time - (start - STREAMING_DELAY));
+        let (start, num_streamable_samples) = loop {
+            let start = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64()
+                + offset;
+            if start >= self.last_transmission_end_time {
+                break (start, len);
+            }
+            // log::debug!("WARNING: cannot send immediately, expecting {}s delay.", self.last_transmission_end_time - (start - offset));
             let time_remaining_in_tx_queue = 1.0_f64 - (self.last_transmission_end_time - start);
             let num_streamable_samples_tmp = time_remaining_in_tx_queue * sample_rate;
             if num_streamable_samples_tmp <= 0.0 {
                 // log::debug!("WARNING: stream start time lies more than one second in the future due to backed up TX queue.");
-                // tx queue fully backed up
-                return Ok(0);
+                // tx queue fully backed up -> wait for it to drain
             } else if end_burst && (num_streamable_samples_tmp as usize) < len {
-                // not enough space in tx queue to send burst in one go -> return and retry later
+                // not enough space in tx queue to send burst in one go -> wait and retry
                 // log::debug!("WARNING: cannot send burst while assuring less than 1s streaming delay.");
-                assert!(len <= (1.0_f64 / sample_rate) as usize); // assure that the burst can be sent at all if tx queue is empty
-                return Ok(0);
             } else if (num_streamable_samples_tmp as usize) < len {
                 // log::debug!("WARNING: tx queue running full, sending only a subset of samples ({}/{}).", num_streamable_samples_tmp, len);
-                num_streamable_samples_tmp as usize
+                break (start, num_streamable_samples_tmp as usize);
             } else {
                 // log::debug!("WARNING: tx queue starting to run full.");
-                len
+                break (start, len);
             }
-        } else {
-            len
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::Timeout(0));
+            }
+            std::thread::sleep((deadline - now).min(TX_POLL_INTERVAL));
         };
         let start = start.max(self.last_transmission_end_time);
         let stop = start + num_streamable_samples as f64 / sample_rate;
@@ -710,11 +854,78 @@ impl crate::TxStreamer for TxStreamer {
 
     fn write_all(
         &mut self,
-        _buffers: &[&[num_complex::Complex32]],
-        _at_ns: Option<i64>,
-        _end_burst: bool,
-        _timeout_us: i64,
+        buffers: &[&[num_complex::Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
     ) -> Result<(), Error> {
-        unimplemented!()
+        debug_assert_eq!(buffers.len(), 1);
+
+        let deadline = Instant::now() + Duration::from_micros(timeout_us.max(0) as u64);
+        let len = buffers[0].len();
+        let mut written = 0;
+
+        while written < len {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let at_ns = if written == 0 { at_ns } else { None };
+            match self.write(
+                &[&buffers[0][written..]],
+                at_ns,
+                end_burst,
+                remaining.as_micros() as i64,
+            ) {
+                Ok(n) => written += n,
+                Err(Error::Timeout(_)) => return Err(Error::Timeout(written)),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the `gaincontrol` mode of the receiver is an automatic one, i.e., anything but `manual`.
+fn gaincontrol_is_agc(mode: &str) -> bool {
+    mode != "manual"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaincontrol() {
+        assert!(!gaincontrol_is_agc("manual"));
+        assert!(gaincontrol_is_agc("peak"));
+        assert!(gaincontrol_is_agc("power"));
+    }
+
+    #[test]
+    fn burst() {
+        use crate::TxStreamer as _;
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+        let mut tx = TxStreamer {
+            agent: Agent::new(),
+            url: "http://127.0.0.1:1".to_string(),
+            frequency: Arc::new(AtomicU64::new(0)),
+            sample_rate: Arc::new(AtomicU64::new(1000)),
+            // half of the one second TX queue is taken
+            last_transmission_end_time: now + STREAMING_DELAY + 0.5,
+            drain_on_deactivate: false,
+            _guard: StreamerGuard::acquire(&Arc::new(AtomicBool::new(false))).unwrap(),
+        };
+        let buf = vec![Complex32::new(0.0, 0.0); 1001];
+        assert!(matches!(
+            tx.write(&[&buf], None, true, 0),
+            Err(Error::ValueError)
+        ));
+        assert!(matches!(
+            tx.write(&[&buf[..900]], None, true, 0),
+            Err(Error::Timeout(0))
+        ));
     }
 }
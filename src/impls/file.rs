@@ -0,0 +1,577 @@
+//! Replay of recorded IQ samples from a file
+use num_complex::Complex32;
+use std::any::Any;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::device::StreamerGuard;
+use crate::Args;
+use crate::CancelToken;
+use crate::DeviceTrait;
+use crate::Direction;
+use crate::Direction::*;
+use crate::Driver;
+use crate::Error;
+use crate::Range;
+use crate::RangeItem;
+use crate::SampleFormat;
+
+const MTU: usize = 8192;
+
+/// File replay driver, serving IQ samples from a file through the [`RxStreamer`] interface.
+///
+/// The device is opened with `driver=file, path=capture.iq`. The optional `format` argument sets
+/// the sample format of the file (`cf32`, `cs16`, or `cs8`; defaults to `cf32`), `sample_rate`
+/// the initial sample rate (defaults to 1e6), and `loop=true` restarts at the beginning of the
+/// file instead of failing at the end.
+///
+/// Samples are paced to the configured sample rate. The center frequency is only recorded and
+/// does not affect the samples. There are no TX channels.
+///
+/// [`RxStreamer`]: crate::RxStreamer
+#[derive(Clone)]
+pub struct FileDevice {
+    path: String,
+    format: SampleFormat,
+    looping: bool,
+    i: Arc<Mutex<Inner>>,
+    rx_busy: Arc<AtomicBool>,
+}
+
+struct Inner {
+    frequency: f64,
+    sample_rate: f64,
+}
+
+/// File replay RX streamer
+///
+/// Samples are converted through a scratch buffer of `MTU` (8192) samples in the file format,
+/// i.e., 64 KiB for `cf32`, which is allocated once, when the streamer is created.
+pub struct RxStreamer {
+    file: BufReader<File>,
+    format: SampleFormat,
+    looping: bool,
+    i: Arc<Mutex<Inner>>,
+    buf: Vec<u8>,
+    start: Option<(Instant, usize)>,
+    cancel: CancelToken,
+    _guard: StreamerGuard,
+}
+
+/// File replay TX streamer (not supported)
+pub struct TxDummy;
+
+impl FileDevice {
+    /// Get the file device described by `args`, which is only non-empty if `driver=file` is
+    /// requested with a `path`.
+    pub fn probe(args: &Args) -> Result<Vec<Args>, Error> {
+        match (args.get::<Driver>("driver"), args.get::<String>("path")) {
+            (Ok(Driver::File), Ok(path)) => {
+                let mut a = Args::new();
+                a.set("driver", Driver::File.to_string());
+                a.set("path", path);
+                Ok(vec![a])
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    /// Open the file given by the `path` argument.
+    pub fn open<A: TryInto<Args>>(args: A) -> Result<Self, Error> {
+        let args: Args = args.try_into().or(Err(Error::ValueError))?;
+        let path = args.get::<String>("path").or(Err(Error::ValueError))?;
+        File::open(&path)?;
+        let format = match args.get::<SampleFormat>("format") {
+            Ok(f) => f,
+            Err(Error::NotFound) => SampleFormat::CF32,
+            Err(e) => return Err(e),
+        };
+        let looping = match args.get::<bool>("loop") {
+            Ok(l) => l,
+            Err(Error::NotFound) => false,
+            Err(e) => return Err(e),
+        };
+        let sample_rate = match args.get::<f64>("sample_rate") {
+            Ok(r) => r,
+            Err(Error::NotFound) => 1e6,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            format,
+            looping,
+            i: Arc::new(Mutex::new(Inner {
+                frequency: 0.0,
+                sample_rate,
+            })),
+            rx_busy: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    fn check(&self, direction: Direction, channel: usize) -> Result<(), Error> {
+        match (direction, channel) {
+            (Rx, 0) => Ok(()),
+            (Rx, _) => Err(Error::InvalidChannel(channel, vec![0])),
+            (Tx, _) => Err(Error::NotSupported),
+        }
+    }
+}
+
+impl DeviceTrait for FileDevice {
+    type RxStreamer = RxStreamer;
+    type TxStreamer = TxDummy;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::File
+    }
+
+    fn id(&self) -> Result<String, Error> {
+        Ok(self.path.clone())
+    }
+
+    fn info(&self) -> Result<Args, Error> {
+        let mut args = Args::new();
+        args.set("driver", Driver::File.to_string());
+        args.set("path", self.path.clone());
+        args.set("format", self.format.to_string());
+        Ok(args)
+    }
+
+    fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
+        match direction {
+            Rx => Ok(1),
+            Tx => Ok(0),
+        }
+    }
+
+    fn full_duplex(&self, _direction: Direction, _channel: usize) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        crate::device::stream_format(&args, &self.supported_formats(Rx)?)?;
+        let guard = StreamerGuard::acquire(&self.rx_busy)?;
+        Ok(RxStreamer {
+            file: BufReader::new(File::open(&self.path)?),
+            format: self.format,
+            looping: self.looping,
+            i: self.i.clone(),
+            buf: vec![0; MTU * self.format.bytes_per_sample()],
+            start: None,
+            cancel: CancelToken::new(),
+            _guard: guard,
+        })
+    }
+
+    fn tx_streamer(&self, _channels: &[usize], _args: Args) -> Result<Self::TxStreamer, Error> {
+        Err(Error::NotSupported)
+    }
+
+    fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.antenna(direction, channel).map(|a| vec![a])
+    }
+
+    fn antenna(&self, direction: Direction, channel: usize) -> Result<String, Error> {
+        self.check(direction, channel)?;
+        Ok("RX".to_string())
+    }
+
+    fn set_antenna(&self, direction: Direction, channel: usize, name: &str) -> Result<(), Error> {
+        if self.antenna(direction, channel)? == name {
+            Ok(())
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check(direction, channel)?;
+        Ok(false)
+    }
+
+    fn enable_agc(&self, direction: Direction, channel: usize, _agc: bool) -> Result<(), Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.check(direction, channel)?;
+        Ok(false)
+    }
+
+    fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.check(direction, channel)?;
+        Ok(Vec::new())
+    }
+
+    fn set_gain(&self, direction: Direction, channel: usize, _gain: f64) -> Result<(), Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn set_gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+        _gain: f64,
+    ) -> Result<(), Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<Option<f64>, Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn gain_element_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<Range, Error> {
+        self.check(direction, channel)?;
+        Err(Error::NotSupported)
+    }
+
+    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.component_frequency_range(direction, channel, "RF")
+    }
+
+    fn frequency(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.component_frequency(direction, channel, "RF")
+    }
+
+    fn set_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        _args: Args,
+    ) -> Result<(), Error> {
+        self.set_component_frequency(direction, channel, "RF", frequency)
+    }
+
+    fn frequency_components(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Vec<String>, Error> {
+        self.check(direction, channel)?;
+        Ok(vec!["RF".to_string()])
+    }
+
+    fn component_frequency_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        self.check(direction, channel)?;
+        if name == "RF" {
+            Ok(Range::new(vec![RangeItem::Interval(0.0, f64::MAX)]))
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<f64, Error> {
+        self.component_frequency_range(direction, channel, name)?;
+        Ok(self.i.lock().unwrap().frequency)
+    }
+
+    fn set_component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        frequency: f64,
+    ) -> Result<(), Error> {
+        let r = self.component_frequency_range(direction, channel, name)?;
+        if r.contains(frequency) {
+            self.i.lock().unwrap().frequency = frequency;
+            Ok(())
+        } else {
+            Err(Error::OutOfRange(r, frequency))
+        }
+    }
+
+    fn sample_rate(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.check(direction, channel)?;
+        Ok(self.i.lock().unwrap().sample_rate)
+    }
+
+    fn set_sample_rate(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: f64,
+    ) -> Result<(), Error> {
+        let r = self.get_sample_rate_range(direction, channel)?;
+        if r.contains(rate) {
+            self.i.lock().unwrap().sample_rate = rate;
+            Ok(())
+        } else {
+            Err(Error::OutOfRange(r, rate))
+        }
+    }
+
+    fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.check(direction, channel)?;
+        Ok(Range::new(vec![RangeItem::Interval(1.0, f64::MAX)]))
+    }
+}
+
+impl RxStreamer {
+    /// Fill the first `len` bytes of the scratch buffer, as far as the file allows.
+    ///
+    /// When looping, a partial sample of `bps` bytes at the end of the file is dropped before
+    /// rewinding, so that the samples stay aligned.
+    fn fill(&mut self, len: usize, bps: usize) -> Result<usize, Error> {
+        let mut got = 0;
+        let mut rewound = false;
+        while got < len {
+            match self.file.read(&mut self.buf[got..len])? {
+                0 if self.looping && !rewound => {
+                    got -= got % bps;
+                    self.file.rewind()?;
+                    rewound = true;
+                }
+                0 => break,
+                n => {
+                    got += n;
+                    rewound = false;
+                }
+            }
+        }
+        Ok(got)
+    }
+}
+
+impl crate::RxStreamer for RxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.cancel.reset();
+        if self.start.is_none() {
+            self.start = Some((Instant::now(), 0));
+        }
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.start = None;
+        Ok(())
+    }
+
+    fn read(&mut self, buffers: &mut [&mut [Complex32]], timeout_us: i64) -> Result<usize, Error> {
+        debug_assert_eq!(buffers.len(), 1);
+        let (start, count) = self.start.ok_or(Error::Inactive)?;
+        if self.cancel.is_canceled() {
+            return Ok(0);
+        }
+        let rate = self.i.lock().unwrap().sample_rate;
+
+        // samples that are due until the timeout expires
+        let horizon = start.elapsed() + Duration::from_micros(timeout_us.max(0) as u64);
+        let due = ((horizon.as_secs_f64() * rate) as usize).saturating_sub(count);
+        let n = std::cmp::min(std::cmp::min(buffers[0].len(), MTU), due);
+        if n == 0 {
+            return Err(Error::Timeout(0));
+        }
+
+        let bps = self.format.bytes_per_sample();
+        let n = self.fill(n * bps, bps)? / bps;
+        if n == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        crate::convert::from_bytes(&self.buf[..n * bps], &mut buffers[0][..n], self.format);
+
+        let release = start + Duration::from_secs_f64((count + n) as f64 / rate);
+        self.cancel.sleep_until(release);
+        self.start = Some((start, count + n));
+        Ok(n)
+    }
+
+    fn sample_rate(&self) -> Result<f64, Error> {
+        Ok(self.i.lock().unwrap().sample_rate)
+    }
+
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        Ok(self.cancel.clone())
+    }
+}
+
+impl crate::TxStreamer for TxDummy {
+    fn mtu(&self) -> Result<usize, Error> {
+        unreachable!()
+    }
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        unreachable!()
+    }
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        unreachable!()
+    }
+    fn write(
+        &mut self,
+        _buffers: &[&[Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
+    ) -> Result<usize, Error> {
+        unreachable!()
+    }
+    fn write_all(
+        &mut self,
+        _buffers: &[&[Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
+    ) -> Result<(), Error> {
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Device;
+    use crate::RxStreamer;
+
+    fn capture(name: &str, samples: &[Complex32], format: SampleFormat) -> String {
+        let mut bytes = vec![0; samples.len() * format.bytes_per_sample()];
+        crate::convert::to_bytes(samples, &mut bytes, format);
+        let path = std::env::temp_dir().join(format!("seify-{}-{name}.iq", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn replay() {
+        let samples: Vec<Complex32> = (0..100)
+            .map(|i| Complex32::new(i as f32 / 100.0, -0.5))
+            .collect();
+        let path = capture("replay", &samples, SampleFormat::CS16);
+        let dev = Device::from_args(format!(
+            "driver=file, path={path}, format=cs16, sample_rate=1e8"
+        ))
+        .unwrap();
+        assert!(matches!(dev.tx_streamer(&[0]), Err(Error::NotSupported)));
+
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        rx.activate().unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); 100];
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        for (a, b) in buf.iter().zip(samples.iter()) {
+            assert!((a - b).norm() < 1e-4);
+        }
+        assert!(matches!(
+            rx.read(&mut [&mut buf], 1000000),
+            Err(Error::Io(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn cancel() {
+        let samples = vec![Complex32::new(0.5, 0.5); 1000];
+        let path = capture("cancel", &samples, SampleFormat::CF32);
+        let dev = Device::from_args(format!(
+            "driver=file, path={path}, format=cf32, sample_rate=1e3, loop=true"
+        ))
+        .unwrap();
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        let token = rx.cancel_token().unwrap();
+        rx.activate().unwrap();
+
+        let start = Instant::now();
+        let canceler = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            token.cancel();
+        });
+        // the read returns without waiting for the samples to be due
+        let mut buf = vec![Complex32::new(0.0, 0.0); 1000];
+        assert_eq!(rx.read(&mut [&mut buf], 10_000_000).unwrap(), 1000);
+        assert!(start.elapsed() < Duration::from_millis(500));
+        canceler.join().unwrap();
+        assert!(matches!(
+            rx.read_exact(&mut [&mut buf], 10_000_000),
+            Err(Error::Canceled(0))
+        ));
+
+        rx.activate().unwrap();
+        assert!(rx.read(&mut [&mut buf[..1]], 1_000_000).is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn looping() {
+        let samples = vec![Complex32::new(0.25, 0.5); 10];
+        let path = capture("looping", &samples, SampleFormat::CF32);
+        let dev = FileDevice::open(format!("path={path}, loop=true, sample_rate=1e8")).unwrap();
+        let mut rx = dev.rx_streamer(&[0], Args::new()).unwrap();
+        rx.activate().unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); 25];
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        assert!(buf.iter().all(|s| *s == samples[0]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn looping_partial() {
+        let samples: Vec<Complex32> = (0..3).map(|i| Complex32::new(i as f32, 0.0)).collect();
+        let path = capture("looping_partial", &samples, SampleFormat::CF32);
+        // append half a sample
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.extend_from_slice(&[0xff; 4]);
+        std::fs::write(&path, bytes).unwrap();
+
+        let dev = FileDevice::open(format!("path={path}, loop=true, sample_rate=1e8")).unwrap();
+        let mut rx = dev.rx_streamer(&[0], Args::new()).unwrap();
+        rx.activate().unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); 10];
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        for (i, s) in buf.iter().enumerate() {
+            assert_eq!(*s, samples[i % 3]);
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
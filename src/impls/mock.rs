@@ -0,0 +1,765 @@
+//! Software-only mock device for testing without hardware
+use num_complex::Complex32;
+use std::any::Any;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::device::StreamerGuard;
+use crate::Args;
+use crate::CancelToken;
+use crate::DeviceTrait;
+use crate::Direction;
+use crate::Direction::*;
+use crate::Driver;
+use crate::Error;
+use crate::Range;
+use crate::RangeItem;
+use crate::StreamStats;
+use crate::StreamerConfig;
+
+const MTU: usize = 8192;
+/// amplitude of the generated tone
+const AMPLITUDE: f32 = 0.5;
+
+/// Mock driver, implementing the [`DeviceTrait`] in software.
+///
+/// The device has one RX and one TX channel. RX streams a deterministic tone at an offset of
+/// `tone` Hz (defaults to 1 kHz) from the center frequency. Uniform noise with an amplitude of
+/// `noise` (defaults to 0) can be added. The noise comes from a fixed-seed generator, i.e., it is
+/// deterministic as well. TX streams are discarded.
+///
+/// The device has to be requested explicitly with `driver=mock`, e.g.,
+/// `Device::from_args("driver=mock, tone=1e3")`.
+#[derive(Clone)]
+pub struct MockDevice {
+    tone: f64,
+    noise: f32,
+    rx: Arc<Mutex<Channel>>,
+    tx: Arc<Mutex<Channel>>,
+    rx_busy: Arc<AtomicBool>,
+    tx_busy: Arc<AtomicBool>,
+    transmitted: Arc<AtomicUsize>,
+}
+
+struct Channel {
+    frequency: f64,
+    sample_rate: f64,
+    bandwidth: f64,
+    gain: f64,
+    agc: bool,
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self {
+            frequency: 100e6,
+            sample_rate: 1e6,
+            bandwidth: 1e6,
+            gain: 0.0,
+            agc: false,
+        }
+    }
+}
+
+/// Mock RX Streamer
+pub struct RxStreamer {
+    channel: Arc<Mutex<Channel>>,
+    tone: f64,
+    noise: f32,
+    phase: f64,
+    rng: u64,
+    active: bool,
+    stats: StreamStats,
+    cancel: CancelToken,
+    config: StreamerConfig,
+    _guard: StreamerGuard,
+}
+
+/// Mock TX Streamer
+pub struct TxStreamer {
+    transmitted: Arc<AtomicUsize>,
+    active: bool,
+    config: StreamerConfig,
+    _guard: StreamerGuard,
+}
+
+impl fmt::Debug for RxStreamer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RxStreamer")
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for TxStreamer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxStreamer")
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockDevice {
+    /// Get a list of mock devices, which is only non-empty if `driver=mock` is requested.
+    pub fn probe(args: &Args) -> Result<Vec<Args>, Error> {
+        match args.get::<Driver>("driver") {
+            Ok(Driver::Mock) => {
+                let mut a = Args::new();
+                a.set("driver", Driver::Mock.to_string());
+                Ok(vec![a])
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    /// Create a mock device.
+    pub fn open<A: TryInto<Args>>(args: A) -> Result<Self, Error> {
+        let args: Args = args.try_into().or(Err(Error::ValueError))?;
+        let tone = match args.get::<f64>("tone") {
+            Ok(t) => t,
+            Err(Error::NotFound) => 1e3,
+            Err(e) => return Err(e),
+        };
+        let noise = match args.get::<f32>("noise") {
+            Ok(n) => n,
+            Err(Error::NotFound) => 0.0,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            tone,
+            noise,
+            rx: Arc::new(Mutex::new(Channel::default())),
+            tx: Arc::new(Mutex::new(Channel::default())),
+            rx_busy: Arc::new(AtomicBool::new(false)),
+            tx_busy: Arc::new(AtomicBool::new(false)),
+            transmitted: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+    /// Number of samples that were written to TX streamers of this device.
+    pub fn transmitted(&self) -> usize {
+        self.transmitted.load(Ordering::Relaxed)
+    }
+
+    fn channel(&self, direction: Direction, channel: usize) -> Result<&Arc<Mutex<Channel>>, Error> {
+        match (direction, channel) {
+            (Rx, 0) => Ok(&self.rx),
+            (Tx, 0) => Ok(&self.tx),
+            _ => Err(Error::InvalidChannel(channel, vec![0])),
+        }
+    }
+}
+
+fn set_checked(value: &mut f64, range: Range, v: f64) -> Result<(), Error> {
+    if range.contains(v) {
+        *value = v;
+        Ok(())
+    } else {
+        Err(Error::OutOfRange(range, v))
+    }
+}
+
+impl DeviceTrait for MockDevice {
+    type RxStreamer = RxStreamer;
+    type TxStreamer = TxStreamer;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Mock
+    }
+
+    fn id(&self) -> Result<String, Error> {
+        Ok("mock".to_string())
+    }
+
+    fn info(&self) -> Result<Args, Error> {
+        let mut args = Args::new();
+        args.set("driver", Driver::Mock.to_string());
+        args.set("tone", self.tone.to_string());
+        args.set("noise", self.noise.to_string());
+        Ok(args)
+    }
+
+    fn num_channels(&self, _direction: Direction) -> Result<usize, Error> {
+        Ok(1)
+    }
+
+    fn full_duplex(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.channel(direction, channel)?;
+        Ok(true)
+    }
+
+    fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        let format = crate::device::stream_format(&args, &self.supported_formats(Rx)?)?;
+        Ok(RxStreamer {
+            channel: self.rx.clone(),
+            tone: self.tone,
+            noise: self.noise,
+            phase: 0.0,
+            rng: 0x853c_49e6_748f_ea9b,
+            active: false,
+            stats: StreamStats::default(),
+            cancel: CancelToken::new(),
+            config: StreamerConfig {
+                buffer_size: MTU,
+                num_transfers: None,
+                channels: channels.to_vec(),
+                format,
+            },
+            _guard: StreamerGuard::acquire(&self.rx_busy)?,
+        })
+    }
+
+    fn tx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::TxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Tx)?)?;
+        let format = crate::device::stream_format(&args, &self.supported_formats(Tx)?)?;
+        Ok(TxStreamer {
+            transmitted: self.transmitted.clone(),
+            active: false,
+            config: StreamerConfig {
+                buffer_size: MTU,
+                num_transfers: None,
+                channels: channels.to_vec(),
+                format,
+            },
+            _guard: StreamerGuard::acquire(&self.tx_busy)?,
+        })
+    }
+
+    fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.antenna(direction, channel).map(|a| vec![a])
+    }
+
+    fn antenna(&self, direction: Direction, channel: usize) -> Result<String, Error> {
+        self.channel(direction, channel)?;
+        match direction {
+            Rx => Ok("RX".to_string()),
+            Tx => Ok("TX".to_string()),
+        }
+    }
+
+    fn set_antenna(&self, direction: Direction, channel: usize, name: &str) -> Result<(), Error> {
+        if self.antenna(direction, channel)? == name {
+            Ok(())
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.channel(direction, channel)?;
+        Ok(matches!(direction, Rx))
+    }
+
+    fn enable_agc(&self, direction: Direction, channel: usize, agc: bool) -> Result<(), Error> {
+        if self.supports_agc(direction, channel)? {
+            self.channel(direction, channel)?.lock().unwrap().agc = agc;
+            Ok(())
+        } else {
+            Err(Error::NotSupported)
+        }
+    }
+
+    fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        Ok(self.channel(direction, channel)?.lock().unwrap().agc)
+    }
+
+    fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.channel(direction, channel)?;
+        Ok(vec!["GAIN".to_string()])
+    }
+
+    fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
+        self.set_gain_element(direction, channel, "GAIN", gain)
+    }
+
+    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.gain_element(direction, channel, "GAIN")
+    }
+
+    fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.gain_element_range(direction, channel, "GAIN")
+    }
+
+    fn set_gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        gain: f64,
+    ) -> Result<(), Error> {
+        let range = self.gain_element_range(direction, channel, name)?;
+        let mut c = self.channel(direction, channel)?.lock().unwrap();
+        set_checked(&mut c.gain, range, gain)
+    }
+
+    fn gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Option<f64>, Error> {
+        self.gain_element_range(direction, channel, name)?;
+        let c = self.channel(direction, channel)?.lock().unwrap();
+        if c.agc {
+            Ok(None)
+        } else {
+            Ok(Some(c.gain))
+        }
+    }
+
+    fn gain_element_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        self.channel(direction, channel)?;
+        if name == "GAIN" {
+            Ok(Range::new(vec![RangeItem::Interval(0.0, 70.0)]))
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.component_frequency_range(direction, channel, "RF")
+    }
+
+    fn frequency(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.component_frequency(direction, channel, "RF")
+    }
+
+    fn set_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        _args: Args,
+    ) -> Result<(), Error> {
+        self.set_component_frequency(direction, channel, "RF", frequency)
+    }
+
+    fn frequency_components(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Vec<String>, Error> {
+        self.channel(direction, channel)?;
+        Ok(vec!["RF".to_string()])
+    }
+
+    fn component_frequency_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<Range, Error> {
+        self.channel(direction, channel)?;
+        if name == "RF" {
+            Ok(Range::new(vec![RangeItem::Interval(0.0, 6e9)]))
+        } else {
+            Err(Error::ValueError)
+        }
+    }
+
+    fn component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+    ) -> Result<f64, Error> {
+        self.component_frequency_range(direction, channel, name)?;
+        Ok(self.channel(direction, channel)?.lock().unwrap().frequency)
+    }
+
+    fn set_component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        name: &str,
+        frequency: f64,
+    ) -> Result<(), Error> {
+        let range = self.component_frequency_range(direction, channel, name)?;
+        let mut c = self.channel(direction, channel)?.lock().unwrap();
+        set_checked(&mut c.frequency, range, frequency)
+    }
+
+    fn sample_rate(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self
+            .channel(direction, channel)?
+            .lock()
+            .unwrap()
+            .sample_rate)
+    }
+
+    fn set_sample_rate(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: f64,
+    ) -> Result<(), Error> {
+        let range = self.get_sample_rate_range(direction, channel)?;
+        let mut c = self.channel(direction, channel)?.lock().unwrap();
+        set_checked(&mut c.sample_rate, range, rate)
+    }
+
+    fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.channel(direction, channel)?;
+        Ok(Range::new(vec![RangeItem::Interval(1e3, 100e6)]))
+    }
+
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self.channel(direction, channel)?.lock().unwrap().bandwidth)
+    }
+
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        let range = self.get_bandwidth_range(direction, channel)?;
+        let mut c = self.channel(direction, channel)?.lock().unwrap();
+        set_checked(&mut c.bandwidth, range, bw)
+    }
+
+    fn get_bandwidth_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.channel(direction, channel)?;
+        Ok(Range::new(vec![RangeItem::Interval(1e3, 100e6)]))
+    }
+}
+
+impl RxStreamer {
+    /// xorshift64*, uniform in [-1, 1)
+    fn uniform(&mut self) -> f32 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let r = self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (r >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
+
+impl crate::RxStreamer for RxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.cancel.reset();
+        if !self.active {
+            self.active = true;
+            self.stats = StreamStats::default();
+        }
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.active = false;
+        Ok(())
+    }
+
+    fn read(&mut self, buffers: &mut [&mut [Complex32]], _timeout_us: i64) -> Result<usize, Error> {
+        debug_assert_eq!(buffers.len(), 1);
+        if !self.active {
+            return Err(Error::Inactive);
+        }
+        if self.cancel.is_canceled() {
+            return Ok(0);
+        }
+        let step =
+            2.0 * std::f64::consts::PI * self.tone / self.channel.lock().unwrap().sample_rate;
+        let n = std::cmp::min(buffers[0].len(), MTU);
+        for s in buffers[0][..n].iter_mut() {
+            *s = Complex32::from_polar(AMPLITUDE, self.phase as f32);
+            if self.noise != 0.0 {
+                *s += Complex32::new(self.uniform(), self.uniform()) * self.noise;
+            }
+            self.phase = (self.phase + step) % (2.0 * std::f64::consts::PI);
+        }
+        self.stats.samples += n as u64;
+        Ok(n)
+    }
+
+    fn stats(&self) -> Result<StreamStats, Error> {
+        Ok(self.stats)
+    }
+
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        Ok(self.cancel.clone())
+    }
+
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        Ok(self.config.clone())
+    }
+
+    fn sample_rate(&self) -> Result<f64, Error> {
+        Ok(self.channel.lock().unwrap().sample_rate)
+    }
+}
+
+impl crate::TxStreamer for TxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.active = true;
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        self.active = false;
+        Ok(())
+    }
+
+    fn drain(&mut self, _timeout_us: i64) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        Ok(self.config.clone())
+    }
+
+    fn write(
+        &mut self,
+        buffers: &[&[Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
+    ) -> Result<usize, Error> {
+        debug_assert_eq!(buffers.len(), 1);
+        if !self.active {
+            return Err(Error::Inactive);
+        }
+        let n = std::cmp::min(buffers[0].len(), MTU);
+        self.transmitted.fetch_add(n, Ordering::Relaxed);
+        Ok(n)
+    }
+
+    fn write_all(
+        &mut self,
+        buffers: &[&[Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
+    ) -> Result<(), Error> {
+        debug_assert_eq!(buffers.len(), 1);
+        let mut i = 0;
+        while i < buffers[0].len() {
+            i += self.write(&[&buffers[0][i..]], at_ns, end_burst, timeout_us)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Device;
+    use crate::Gain;
+    use crate::RxStreamer;
+    use crate::TxStreamer;
+
+    #[test]
+    fn tone() {
+        let dev = Device::from_args("driver=mock, tone=1e3").unwrap();
+        assert_eq!(dev.driver(), Driver::Mock);
+        dev.set_sample_rate(Rx, 0, 8e3).unwrap();
+
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); 16];
+        assert!(matches!(rx.read(&mut [&mut buf], 0), Err(Error::Inactive)));
+        rx.activate().unwrap();
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+
+        // 1 kHz at 8 kHz sample rate, i.e., 45° per sample
+        for (i, s) in buf.iter().enumerate() {
+            let e = Complex32::from_polar(AMPLITUDE, std::f32::consts::FRAC_PI_4 * i as f32);
+            assert!((s - e).norm() < 1e-4, "sample {i}: {s} != {e}");
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        let mut buf = vec![Complex32::new(0.0, 0.0); 16];
+        rx.activate().unwrap();
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        assert_eq!(rx.stats().unwrap().samples, 32);
+        rx.deactivate().unwrap();
+        rx.activate().unwrap();
+        assert_eq!(rx.stats().unwrap(), StreamStats::default());
+    }
+
+    #[test]
+    fn read_to_vec() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        assert!(matches!(rx.read_to_vec(16, 1000000), Err(Error::Inactive)));
+        rx.activate().unwrap();
+        let samples = rx.read_to_vec(2 * MTU + 5, 1000000).unwrap();
+        assert_eq!(samples.len(), 2 * MTU + 5);
+        assert!(samples.iter().all(|s| (s.norm() - AMPLITUDE).abs() < 1e-3));
+        assert!(rx.read_to_vec(0, 1000000).unwrap().is_empty());
+    }
+
+    #[test]
+    fn streamer_config() {
+        let dev = MockDevice::open("").unwrap();
+        let rx = dev.rx_streamer(&[0], Args::new()).unwrap();
+        let config = rx.config().unwrap();
+        assert_eq!(config.buffer_size, MTU);
+        assert_eq!(config.channels, vec![0]);
+        assert_eq!(config.format, crate::SampleFormat::CF32);
+        let tx = dev.tx_streamer(&[0], Args::new()).unwrap();
+        assert_eq!(tx.config().unwrap(), config);
+    }
+
+    #[test]
+    fn activate_now() {
+        let dev = MockDevice::open("").unwrap();
+        let mut rx = dev.rx_streamer(&[0], Args::new()).unwrap();
+        let mut tx = dev.tx_streamer(&[0], Args::new()).unwrap();
+        let mut buf = [Complex32::new(0.0, 0.0); 16];
+        let start = std::time::Instant::now();
+        rx.activate_at(None).unwrap();
+        tx.activate_at(None).unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_millis(50));
+        assert_eq!(rx.read(&mut [&mut buf], 0).unwrap(), 16);
+        assert_eq!(tx.write(&[&buf], None, false, 0).unwrap(), 16);
+    }
+
+    #[test]
+    fn activation() {
+        let dev = MockDevice::open("").unwrap();
+        let mut rx = dev.rx_streamer(&[0], Args::new()).unwrap();
+        let mut buf = [Complex32::new(0.0, 0.0); 16];
+        rx.deactivate().unwrap();
+        rx.activate().unwrap();
+        rx.read_exact(&mut [&mut buf], 1000000).unwrap();
+        rx.activate().unwrap();
+        assert_eq!(rx.stats().unwrap().samples, 16);
+        rx.deactivate().unwrap();
+        rx.deactivate().unwrap();
+        assert!(matches!(rx.read(&mut [&mut buf], 0), Err(Error::Inactive)));
+    }
+
+    #[test]
+    fn gain() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        dev.enable_agc(Rx, 0, false).unwrap();
+        assert_eq!(dev.set_gain_actual(Rx, 0, 100.0).unwrap(), Some(70.0));
+        assert!(matches!(
+            dev.try_set_gain(Rx, 0, 100.0),
+            Err(Error::OutOfRange(_, 100.0))
+        ));
+        dev.try_set_gain(Rx, 0, 30.0).unwrap();
+        assert_eq!(dev.gain(Rx, 0).unwrap(), Some(30.0));
+        assert_eq!(dev.min_gain(Rx, 0).unwrap(), 0.0);
+        assert_eq!(dev.max_gain(Rx, 0).unwrap(), 70.0);
+
+        let dev = Device::from_args("driver=mock,rx_gain=100").unwrap();
+        dev.enable_agc(Rx, 0, false).unwrap();
+        assert_eq!(dev.gain(Rx, 0).unwrap(), Some(70.0));
+    }
+
+    #[test]
+    fn gains() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        dev.enable_agc(Rx, 0, false).unwrap();
+        let stage = |name: &str, value| Gain::Stage {
+            name: name.to_string(),
+            value,
+        };
+        dev.set_gains(Rx, 0, &[Gain::Overall(10.0), stage("GAIN", 20.0)])
+            .unwrap();
+        assert_eq!(dev.gain(Rx, 0).unwrap(), Some(20.0));
+
+        let e = dev
+            .set_gains(Rx, 0, &[Gain::Overall(30.0), stage("GAIN", 100.0)])
+            .unwrap_err();
+        assert!(matches!(e, Error::Apply(ref n, _) if n == "GAIN"));
+        assert_eq!(dev.gain(Rx, 0).unwrap(), Some(20.0));
+        assert!(dev.set_gains(Rx, 0, &[stage("LNA", 10.0)]).is_err());
+    }
+
+    #[test]
+    fn bandwidth_auto() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        dev.set_sample_rate(Rx, 0, 2e6).unwrap();
+        dev.set_bandwidth_auto(Rx, 0, None).unwrap();
+        assert_eq!(dev.bandwidth(Rx, 0).unwrap(), 1.5e6);
+        dev.set_sample_rate(Rx, 0, 1e3).unwrap();
+        dev.set_bandwidth_auto(Rx, 0, Some(0.5)).unwrap();
+        assert_eq!(dev.bandwidth(Rx, 0).unwrap(), 1e3);
+    }
+
+    #[test]
+    fn try_clone() {
+        let dev = Device::from_args("driver=mock, tone=1000").unwrap();
+        let clone = dev.try_clone().unwrap();
+        assert_eq!(clone.driver(), Driver::Mock);
+        assert_eq!(clone.info().unwrap(), dev.info().unwrap());
+        clone.set_frequency(Rx, 0, 2.4e9).unwrap();
+        assert_ne!(dev.frequency(Rx, 0).unwrap(), 2.4e9);
+    }
+
+    #[test]
+    fn debug() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        assert_eq!(format!("{dev:?}"), "Device { driver: Mock, .. }");
+        let mut rx = MockDevice::open("")
+            .unwrap()
+            .rx_streamer(&[0], Args::new())
+            .unwrap();
+        rx.activate().unwrap();
+        assert_eq!(format!("{rx:?}"), "RxStreamer { active: true, .. }");
+    }
+
+    #[test]
+    fn open_config() {
+        let dev =
+            Device::from_args("driver=mock, rx_sample_rate=3.2e6, tx_frequency=2.4e9").unwrap();
+        assert_eq!(dev.sample_rate(Rx, 0).unwrap(), 3.2e6);
+        assert_eq!(dev.frequency(Tx, 0).unwrap(), 2.4e9);
+        assert!(Device::from_args("driver=mock, rx_frequency=7e9").is_err());
+    }
+
+    #[test]
+    fn config() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        dev.set_frequency(Rx, 0, 2.4e9).unwrap();
+        assert_eq!(dev.frequency(Rx, 0).unwrap(), 2.4e9);
+        for f in [7e9, -1.0, f64::NAN] {
+            assert!(matches!(
+                dev.set_frequency(Rx, 0, f),
+                Err(Error::OutOfRange(_, _))
+            ));
+        }
+        assert!(matches!(
+            dev.frequency(Rx, 1),
+            Err(Error::InvalidChannel(1, _))
+        ));
+    }
+
+    #[test]
+    fn sink() {
+        let dev = MockDevice::open("").unwrap();
+        let mut tx = dev.tx_streamer(&[0], Args::new()).unwrap();
+        tx.activate().unwrap();
+        let buf = vec![Complex32::new(1.0, 0.0); 3 * MTU / 2];
+        tx.write_all(&[&buf], None, true, 0).unwrap();
+        assert_eq!(dev.transmitted(), buf.len());
+        tx.send_burst(&buf, None).unwrap();
+        assert_eq!(dev.transmitted(), 2 * buf.len());
+    }
+}
@@ -9,6 +9,21 @@ pub mod aaronia_http;
 #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
 pub use aaronia_http::AaroniaHttp;
 
+#[cfg(feature = "file")]
+pub mod file;
+#[cfg(feature = "file")]
+pub use file::FileDevice;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "mock")]
+pub use mock::MockDevice;
+
+#[cfg(feature = "null")]
+pub mod null;
+#[cfg(feature = "null")]
+pub use null::NullDevice;
+
 #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
 pub mod rtlsdr;
 #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
@@ -0,0 +1,351 @@
+//! Null source and sink for benchmarking
+use num_complex::Complex32;
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::Args;
+use crate::DeviceTrait;
+use crate::Direction;
+use crate::Direction::*;
+use crate::Driver;
+use crate::Error;
+use crate::Range;
+use crate::RangeItem;
+
+const MTU: usize = 65536;
+
+/// Null driver, whose RX streamers produce zeros and TX streamers discard samples as fast as
+/// possible.
+///
+/// Useful for benchmarking downstream DSP without USB overhead. All configuration calls succeed
+/// and are only recorded. The device has to be requested explicitly with `driver=null`.
+#[derive(Clone, Debug)]
+pub struct NullDevice {
+    i: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    rx: Channel,
+    tx: Channel,
+}
+
+#[derive(Debug, Default)]
+struct Channel {
+    frequency: f64,
+    sample_rate: f64,
+    gain: f64,
+    agc: bool,
+}
+
+/// Null RX streamer, filling buffers with zeros
+#[derive(Debug)]
+pub struct RxStreamer;
+
+/// Null TX streamer, discarding samples
+#[derive(Debug)]
+pub struct TxStreamer;
+
+impl NullDevice {
+    /// Get a list of null devices, which is only non-empty if `driver=null` is requested.
+    pub fn probe(args: &Args) -> Result<Vec<Args>, Error> {
+        match args.get::<Driver>("driver") {
+            Ok(Driver::Null) => {
+                let mut a = Args::new();
+                a.set("driver", Driver::Null.to_string());
+                Ok(vec![a])
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+    /// Create a null device.
+    pub fn open<A: TryInto<Args>>(_args: A) -> Result<Self, Error> {
+        Ok(Self {
+            i: Arc::new(Mutex::new(Inner::default())),
+        })
+    }
+
+    fn with<T>(
+        &self,
+        direction: Direction,
+        channel: usize,
+        f: impl FnOnce(&mut Channel) -> T,
+    ) -> Result<T, Error> {
+        if channel != 0 {
+            return Err(Error::InvalidChannel(channel, vec![0]));
+        }
+        let mut i = self.i.lock().unwrap();
+        match direction {
+            Rx => Ok(f(&mut i.rx)),
+            Tx => Ok(f(&mut i.tx)),
+        }
+    }
+}
+
+fn any() -> Range {
+    Range::new(vec![RangeItem::Interval(f64::MIN, f64::MAX)])
+}
+
+impl DeviceTrait for NullDevice {
+    type RxStreamer = RxStreamer;
+    type TxStreamer = TxStreamer;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Null
+    }
+
+    fn id(&self) -> Result<String, Error> {
+        Ok("null".to_string())
+    }
+
+    fn info(&self) -> Result<Args, Error> {
+        let mut args = Args::new();
+        args.set("driver", Driver::Null.to_string());
+        Ok(args)
+    }
+
+    fn num_channels(&self, _direction: Direction) -> Result<usize, Error> {
+        Ok(1)
+    }
+
+    fn full_duplex(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.with(direction, channel, |_| true)
+    }
+
+    fn rx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        Ok(RxStreamer)
+    }
+
+    fn tx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::TxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Tx)?)?;
+        Ok(TxStreamer)
+    }
+
+    fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.antenna(direction, channel).map(|a| vec![a])
+    }
+
+    fn antenna(&self, direction: Direction, channel: usize) -> Result<String, Error> {
+        self.with(direction, channel, |_| "NULL".to_string())
+    }
+
+    fn set_antenna(&self, direction: Direction, channel: usize, _name: &str) -> Result<(), Error> {
+        self.with(direction, channel, |_| ())
+    }
+
+    fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.with(direction, channel, |_| true)
+    }
+
+    fn enable_agc(&self, direction: Direction, channel: usize, agc: bool) -> Result<(), Error> {
+        self.with(direction, channel, |c| c.agc = agc)
+    }
+
+    fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.with(direction, channel, |c| c.agc)
+    }
+
+    fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
+        self.with(direction, channel, |_| vec!["NULL".to_string()])
+    }
+
+    fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
+        self.with(direction, channel, |c| c.gain = gain)
+    }
+
+    fn gain(&self, direction: Direction, channel: usize) -> Result<Option<f64>, Error> {
+        self.with(direction, channel, |c| (!c.agc).then_some(c.gain))
+    }
+
+    fn gain_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.with(direction, channel, |_| any())
+    }
+
+    fn set_gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+        gain: f64,
+    ) -> Result<(), Error> {
+        self.set_gain(direction, channel, gain)
+    }
+
+    fn gain_element(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<Option<f64>, Error> {
+        self.gain(direction, channel)
+    }
+
+    fn gain_element_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<Range, Error> {
+        self.gain_range(direction, channel)
+    }
+
+    fn frequency_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.with(direction, channel, |_| any())
+    }
+
+    fn frequency(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.with(direction, channel, |c| c.frequency)
+    }
+
+    fn set_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: f64,
+        _args: Args,
+    ) -> Result<(), Error> {
+        self.with(direction, channel, |c| c.frequency = frequency)
+    }
+
+    fn frequency_components(
+        &self,
+        direction: Direction,
+        channel: usize,
+    ) -> Result<Vec<String>, Error> {
+        self.with(direction, channel, |_| vec!["NULL".to_string()])
+    }
+
+    fn component_frequency_range(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<Range, Error> {
+        self.frequency_range(direction, channel)
+    }
+
+    fn component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+    ) -> Result<f64, Error> {
+        self.frequency(direction, channel)
+    }
+
+    fn set_component_frequency(
+        &self,
+        direction: Direction,
+        channel: usize,
+        _name: &str,
+        frequency: f64,
+    ) -> Result<(), Error> {
+        self.set_frequency(direction, channel, frequency, Args::new())
+    }
+
+    fn sample_rate(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        self.with(direction, channel, |c| c.sample_rate)
+    }
+
+    fn set_sample_rate(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: f64,
+    ) -> Result<(), Error> {
+        self.with(direction, channel, |c| c.sample_rate = rate)
+    }
+
+    fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        self.with(direction, channel, |_| any())
+    }
+}
+
+impl crate::RxStreamer for RxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn read(&mut self, buffers: &mut [&mut [Complex32]], _timeout_us: i64) -> Result<usize, Error> {
+        let n = buffers.iter().map(|b| b.len()).min().unwrap_or(0).min(MTU);
+        for b in buffers.iter_mut() {
+            b[..n].fill(Complex32::new(0.0, 0.0));
+        }
+        Ok(n)
+    }
+}
+
+impl crate::TxStreamer for TxStreamer {
+    fn mtu(&self) -> Result<usize, Error> {
+        Ok(MTU)
+    }
+
+    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn write(
+        &mut self,
+        buffers: &[&[Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
+    ) -> Result<usize, Error> {
+        Ok(buffers.iter().map(|b| b.len()).min().unwrap_or(0).min(MTU))
+    }
+
+    fn write_all(
+        &mut self,
+        _buffers: &[&[Complex32]],
+        _at_ns: Option<i64>,
+        _end_burst: bool,
+        _timeout_us: i64,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn drain(&mut self, _timeout_us: i64) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Device;
+    use crate::RxStreamer;
+
+    #[test]
+    fn zeros() {
+        let dev = Device::from_args("driver=null").unwrap();
+        dev.set_frequency(Tx, 0, 123e6).unwrap();
+        assert_eq!(dev.frequency(Tx, 0).unwrap(), 123e6);
+
+        let mut rx = dev.rx_streamer(&[0]).unwrap();
+        let mut buf = vec![Complex32::new(1.0, 1.0); 1024];
+        assert_eq!(rx.read(&mut [&mut buf], 0).unwrap(), 1024);
+        assert!(buf.iter().all(|s| *s == Complex32::new(0.0, 0.0)));
+    }
+}
@@ -4,10 +4,16 @@ use seify_rtlsdr::enumerate;
 use seify_rtlsdr::RtlSdr as Sdr;
 use seify_rtlsdr::TunerGain;
 use std::any::Any;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
+use crate::device::StreamerGuard;
+use crate::logging::LogContext;
 use crate::Args;
+use crate::CancelToken;
 use crate::DeviceTrait;
 use crate::Direction;
 use crate::Direction::*;
@@ -15,38 +21,118 @@ use crate::Driver;
 use crate::Error;
 use crate::Range;
 use crate::RangeItem;
+use crate::SampleFormat;
+use crate::StreamStats;
+use crate::StreamerConfig;
 
 const MTU: usize = 4 * 16384;
+/// USB bulk transfers of the RTL-SDR have to be a multiple of this size (in bytes).
+const TRANSFER_ALIGNMENT: usize = 512;
+/// Conservative time for the tuner PLL to lock after retuning.
+const TUNE_SETTLING_TIME: Duration = Duration::from_millis(10);
+/// Default number of retries if opening the device fails transiently.
+const OPEN_RETRIES: usize = 2;
+/// Default delay before the first retry, doubled for each further retry.
+const OPEN_RETRY_DELAY: Duration = Duration::from_millis(100);
 
 /// Rusty RTL-SDR driver
 #[derive(Clone)]
 pub struct RtlSdr {
     dev: Arc<Sdr>,
     index: usize,
+    serial: String,
     i: Arc<Mutex<Inner>>,
+    rx_busy: Arc<AtomicBool>,
+    log: LogContext,
 }
 unsafe impl Send for RtlSdr {}
 unsafe impl Sync for RtlSdr {}
 
+impl fmt::Debug for RtlSdr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RtlSdr")
+            .field("index", &self.index)
+            .field("serial", &self.serial)
+            .finish_non_exhaustive()
+    }
+}
+
 struct Inner {
     gain: TunerGain,
+    bias_tee: bool,
 }
 
 /// Rusty RTL-SDR RX streamer
+///
+/// The size of the transfer buffer in bytes can be set through the `buffer_size` argument when
+/// creating the streamer. It has to be a multiple of 512 and defaults to 65536.
+///
+/// The transfer buffer is allocated once, when the streamer is created, and reused to convert
+/// the CS8 samples of the device, i.e., reads do not allocate. The same holds for
+/// [`read_raw`](crate::RxStreamer::read_raw) in other formats, which converts through a scratch
+/// buffer of one MTU. USB transfers are multiples of 512
+/// bytes, so reads of fewer than 256 samples fetch one transfer and keep the remainder in the
+/// buffer for the next reads.
+///
+/// Only one streamer can exist at a time.
 pub struct RxStreamer {
     dev: Arc<Sdr>,
-    buf: [u8; MTU],
+    buf: Vec<u8>,
+    /// Bytes of `buf` that were transferred but not read yet.
+    pending: std::ops::Range<usize>,
+    /// Samples for the conversion of raw reads to formats other than CS8.
+    scratch: Vec<Complex32>,
+    log: LogContext,
+    stats: StreamStats,
+    active: bool,
+    cancel: CancelToken,
+    config: StreamerConfig,
+    _guard: StreamerGuard,
 }
 
 unsafe impl Send for RxStreamer {}
 
 impl RxStreamer {
-    fn new(dev: Arc<Sdr>) -> Self {
-        Self { dev, buf: [0; MTU] }
+    fn new(
+        dev: Arc<Sdr>,
+        buffer_size: usize,
+        format: SampleFormat,
+        log: LogContext,
+        guard: StreamerGuard,
+    ) -> Self {
+        // The buffer holds interleaved 8-bit I/Q, i.e., two bytes per sample.
+        let config = StreamerConfig {
+            buffer_size: buffer_size / 2,
+            num_transfers: None,
+            channels: vec![0],
+            format,
+        };
+        Self {
+            dev,
+            buf: vec![0; buffer_size],
+            pending: 0..0,
+            scratch: vec![Complex32::new(0.0, 0.0); buffer_size / 2],
+            log,
+            stats: StreamStats::default(),
+            active: false,
+            cancel: CancelToken::new(),
+            config,
+            _guard: guard,
+        }
+    }
+}
+
+impl fmt::Debug for RxStreamer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RxStreamer")
+            .field("device", &format_args!("{}", self.log))
+            .field("active", &self.active)
+            .finish_non_exhaustive()
     }
 }
 
 /// Rusty RTL-SDR TX dummy streamer
+#[derive(Debug)]
 pub struct TxDummy;
 unsafe impl Send for TxDummy {}
 
@@ -56,38 +142,135 @@ impl RtlSdr {
     /// The returned [`Args`] specify the device, i.e., passing them to [`RtlSdr::open`] will open
     /// this particular device. At the moment, this just uses the index in the list of devices
     /// returned by the driver.
-    pub fn probe(_args: &Args) -> Result<Vec<Args>, Error> {
+    ///
+    /// The `index` and `serial` arguments can be used to filter the devices.
+    pub fn probe(args: &Args) -> Result<Vec<Args>, Error> {
+        let index = args.get::<usize>("index").ok();
+        let serial = args.get::<String>("serial").ok();
         let rtls = enumerate().or(Err(Error::DeviceError))?;
         let mut devs = Vec::new();
         for r in rtls {
-            devs.push(format!("driver=rtlsdr, index={}", r.index).try_into()?);
+            if index.is_some_and(|i| i != r.index)
+                || serial.as_ref().is_some_and(|s| *s != r.serial)
+            {
+                continue;
+            }
+            let mut a = Args::new();
+            a.set("driver", Driver::RtlSdr.to_string());
+            a.set("index", r.index.to_string());
+            a.set("serial", r.serial);
+            devs.push(a);
         }
         Ok(devs)
     }
     /// Create an RTL-SDR device
     ///
-    /// At the moment, only an `index` argument is considered, which defines the index of the
-    /// devices in the list returned by the driver.
+    /// The `index` argument defines the index of the devices in the list returned by the driver.
+    /// Alternatively, the device can be selected by its `serial`. If both are given, they have to
+    /// refer to the same device.
+    /// Setting `bias_tee=true` powers the antenna port right after opening the device.
+    ///
+    /// Opening a device that was just plugged in or reset can fail transiently. This is retried
+    /// `open_retries` times (default 2), waiting `open_retry_delay_ms` (default 100) before the
+    /// first retry and twice as long before each further one.
     pub fn open<A: TryInto<Args>>(args: A) -> Result<Self, Error> {
         let args = args.try_into().or(Err(Error::ValueError))?;
-        let index = args.get::<usize>("index").unwrap_or(0);
-        let rtls = enumerate().or(Err(Error::DeviceError))?;
-        if index >= rtls.len() {
-            return Err(Error::NotFound);
+        let bias_tee = args.get::<bool>("bias_tee").unwrap_or(false);
+        let retries = match args.get::<usize>("open_retries") {
+            Err(Error::NotFound) => OPEN_RETRIES,
+            r => r?,
+        };
+        let delay = match args.get::<u64>("open_retry_delay_ms") {
+            Err(Error::NotFound) => OPEN_RETRY_DELAY,
+            r => Duration::from_millis(r?),
+        };
+        if let (Ok(serial), Ok(_)) = (args.get::<String>("serial"), args.get::<usize>("index")) {
+            let mut by_index = args.clone();
+            by_index.remove("serial");
+            if let Some(a) = Self::probe(&by_index)?.first() {
+                if a.get::<String>("serial")? != serial {
+                    return Err(Error::ValueError);
+                }
+            }
         }
+        let (index, serial) = match Self::probe(&args)?.first() {
+            Some(a) => (a.get::<usize>("index")?, a.get::<String>("serial")?),
+            None => return Err(Error::NotFound),
+        };
         #[allow(clippy::arc_with_non_send_sync)]
-        let dev = Arc::new(Sdr::open(index)?);
-        dev.set_tuner_gain(TunerGain::Auto)?;
-        dev.set_bias_tee(false)?;
+        let dev = Arc::new(retry(retries, delay, || {
+            let dev = Sdr::open(index)?;
+            dev.set_tuner_gain(TunerGain::Auto)?;
+            dev.set_bias_tee(bias_tee)?;
+            Ok(dev)
+        })?);
+        let log = LogContext::new(Driver::RtlSdr, "serial", serial.clone());
         let dev = RtlSdr {
             dev,
             index,
+            serial,
             i: Arc::new(Mutex::new(Inner {
                 gain: TunerGain::Auto,
+                bias_tee,
             })),
+            rx_busy: Arc::new(AtomicBool::new(false)),
+            log,
         };
         Ok(dev)
     }
+    /// Check that the `channel` exists before it is passed to the device.
+    fn validate_channel(&self, direction: Direction, channel: usize) -> Result<(), Error> {
+        check_channel(self.num_channels(direction)?, channel)
+    }
+}
+
+/// Whether opening the device might succeed if retried, e.g., while it is still enumerating.
+///
+/// [`Error::Busy`] is not transient here, since the device is claimed by another user, who will
+/// not release it within the backoff.
+fn is_transient(e: &Error) -> bool {
+    use seify_rtlsdr::error::RtlsdrError::Usb;
+    (e.is_retryable() && !matches!(e, Error::Busy))
+        || matches!(
+            e,
+            Error::RtlSdr(Usb(rusb::Error::Io
+                | rusb::Error::Pipe
+                | rusb::Error::Timeout
+                | rusb::Error::Interrupted))
+        )
+}
+
+/// Call `f` until it succeeds, retrying up to `retries` times on transient errors with
+/// exponential backoff, starting at `delay`.
+fn retry<T>(
+    retries: usize,
+    mut delay: Duration,
+    mut f: impl FnMut() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                attempt += 1;
+                log::debug!("opening rtlsdr failed ({e}), retry {attempt}/{retries} in {delay:?}");
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            r => return r,
+        }
+    }
+}
+
+/// Fails with [`Error::NotSupported`] for a direction without channels and with
+/// [`Error::InvalidChannel`] for a `channel` out of range.
+fn check_channel(num_channels: usize, channel: usize) -> Result<(), Error> {
+    if num_channels == 0 {
+        Err(Error::NotSupported)
+    } else if channel >= num_channels {
+        Err(Error::InvalidChannel(channel, (0..num_channels).collect()))
+    } else {
+        Ok(())
+    }
 }
 
 impl DeviceTrait for RtlSdr {
@@ -111,7 +294,11 @@ impl DeviceTrait for RtlSdr {
     }
 
     fn info(&self) -> Result<Args, Error> {
-        format!("driver=rtlsdr, index={}", self.index).try_into()
+        let mut args = Args::new();
+        args.set("driver", Driver::RtlSdr.to_string());
+        args.set("index", self.index.to_string());
+        args.set("serial", self.serial.clone());
+        Ok(args)
     }
 
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
@@ -125,89 +312,117 @@ impl DeviceTrait for RtlSdr {
         Ok(false)
     }
 
-    fn rx_streamer(&self, channels: &[usize], _args: Args) -> Result<Self::RxStreamer, Error> {
-        if channels != [0] {
-            Err(Error::ValueError)
-        } else {
-            Ok(RxStreamer::new(self.dev.clone()))
+    fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        match key {
+            "bias_tee" => {
+                let enabled = value.parse::<bool>().or(Err(Error::ValueError))?;
+                self.set_bias_tee(Rx, 0, enabled)
+            }
+            _ => Err(Error::NotSupported),
+        }
+    }
+
+    fn feature(&self, key: &str) -> Result<String, Error> {
+        match key {
+            "bias_tee" => Ok(self.bias_tee(Rx, 0)?.to_string()),
+            _ => Err(Error::NotSupported),
+        }
+    }
+
+    fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::check_channels(channels, self.num_channels(Rx)?)?;
+        let format = crate::device::stream_format(&args, &self.supported_formats(Rx)?)?;
+        let buffer_size = match args.get::<usize>("buffer_size") {
+            Ok(s) => s,
+            Err(Error::NotFound) => MTU,
+            Err(e) => return Err(e),
+        };
+        if buffer_size == 0 || buffer_size % TRANSFER_ALIGNMENT != 0 {
+            return Err(Error::ValueError);
         }
+        let guard = StreamerGuard::acquire(&self.rx_busy)?;
+        Ok(RxStreamer::new(
+            self.dev.clone(),
+            buffer_size,
+            format,
+            self.log.clone(),
+            guard,
+        ))
     }
 
     fn tx_streamer(&self, _channels: &[usize], _args: Args) -> Result<Self::TxStreamer, Error> {
         Err(Error::NotSupported)
     }
 
+    fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        match direction {
+            Rx => Ok(vec![SampleFormat::CF32, SampleFormat::CS8]),
+            Tx => Err(Error::NotSupported),
+        }
+    }
+
     fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         self.antenna(direction, channel).map(|a| vec![a])
     }
 
     fn antenna(&self, direction: Direction, channel: usize) -> Result<String, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok("RX".to_string())
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok("RX".to_string())
     }
 
     fn set_antenna(&self, direction: Direction, channel: usize, name: &str) -> Result<(), Error> {
-        if matches!(direction, Rx) && channel == 0 && name == "RX" {
+        self.validate_channel(direction, channel)?;
+        if name == "RX" {
             Ok(())
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
+    fn set_bias_tee(
+        &self,
+        direction: Direction,
+        channel: usize,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.validate_channel(direction, channel)?;
+        self.dev.set_bias_tee(enabled)?;
+        self.i.lock().unwrap().bias_tee = enabled;
+        Ok(())
+    }
+
+    fn bias_tee(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
+        self.validate_channel(direction, channel)?;
+        Ok(self.i.lock().unwrap().bias_tee)
+    }
+
     fn gain_elements(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok(vec!["TUNER".to_string()])
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok(vec!["TUNER".to_string()])
     }
 
     fn supports_agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok(true)
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok(true)
     }
 
     fn enable_agc(&self, direction: Direction, channel: usize, agc: bool) -> Result<(), Error> {
         let gains = self.dev.get_tuner_gains().or(Err(Error::DeviceError))?;
-        if matches!(direction, Rx) && channel == 0 {
-            let mut inner = self.i.lock().unwrap();
-            if agc {
-                inner.gain = TunerGain::Auto;
-                Ok(self.dev.set_tuner_gain(inner.gain.clone())?)
-            } else {
-                inner.gain = TunerGain::Manual(gains[gains.len() / 2]);
-                Ok(self.dev.set_tuner_gain(inner.gain.clone())?)
-            }
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
+        self.validate_channel(direction, channel)?;
+        let mut inner = self.i.lock().unwrap();
+        if agc {
+            inner.gain = TunerGain::Auto;
+            Ok(self.dev.set_tuner_gain(inner.gain.clone())?)
         } else {
-            Err(Error::NotSupported)
+            inner.gain = TunerGain::Manual(gains[gains.len() / 2]);
+            Ok(self.dev.set_tuner_gain(inner.gain.clone())?)
         }
     }
 
     fn agc(&self, direction: Direction, channel: usize) -> Result<bool, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            let inner = self.i.lock().unwrap();
-            Ok(matches!(inner.gain, TunerGain::Auto))
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        let inner = self.i.lock().unwrap();
+        Ok(matches!(inner.gain, TunerGain::Auto))
     }
 
     fn set_gain(&self, direction: Direction, channel: usize, gain: f64) -> Result<(), Error> {
@@ -235,7 +450,10 @@ impl DeviceTrait for RtlSdr {
             inner.gain = TunerGain::Manual((gain * 10.0) as i32);
             Ok(self.dev.set_tuner_gain(inner.gain.clone())?)
         } else {
-            log::warn!("Gain out of range");
+            log::warn!(
+                "{}: gain out of range",
+                self.log.channel(direction, channel)
+            );
             Err(Error::OutOfRange(r, gain))
         }
     }
@@ -246,16 +464,15 @@ impl DeviceTrait for RtlSdr {
         channel: usize,
         name: &str,
     ) -> Result<Option<f64>, Error> {
-        if matches!(direction, Rx) && channel == 0 && name == "TUNER" {
+        self.validate_channel(direction, channel)?;
+        if name == "TUNER" {
             let inner = self.i.lock().unwrap();
             match inner.gain {
                 TunerGain::Auto => Ok(None),
                 TunerGain::Manual(i) => Ok(Some(i as f64)),
             }
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
@@ -265,12 +482,11 @@ impl DeviceTrait for RtlSdr {
         channel: usize,
         name: &str,
     ) -> Result<Range, Error> {
-        if matches!(direction, Rx) && channel == 0 && name == "TUNER" {
+        self.validate_channel(direction, channel)?;
+        if name == "TUNER" {
             Ok(Range::new(vec![RangeItem::Interval(0.0, 50.0)]))
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
@@ -292,18 +508,18 @@ impl DeviceTrait for RtlSdr {
         self.set_component_frequency(direction, channel, "TUNER", frequency)
     }
 
+    fn tune_settling_time(&self, direction: Direction, channel: usize) -> Result<Duration, Error> {
+        self.validate_channel(direction, channel)?;
+        Ok(TUNE_SETTLING_TIME)
+    }
+
     fn frequency_components(
         &self,
         direction: Direction,
         channel: usize,
     ) -> Result<Vec<String>, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok(vec!["TUNER".to_string()])
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok(vec!["TUNER".to_string()])
     }
 
     fn component_frequency_range(
@@ -312,12 +528,11 @@ impl DeviceTrait for RtlSdr {
         channel: usize,
         name: &str,
     ) -> Result<Range, Error> {
-        if matches!(direction, Rx) && channel == 0 && name == "TUNER" {
+        self.validate_channel(direction, channel)?;
+        if name == "TUNER" {
             Ok(Range::new(vec![RangeItem::Interval(0.0, 2e9)]))
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
@@ -327,12 +542,11 @@ impl DeviceTrait for RtlSdr {
         channel: usize,
         name: &str,
     ) -> Result<f64, Error> {
-        if matches!(direction, Rx) && channel == 0 && name == "TUNER" {
+        self.validate_channel(direction, channel)?;
+        if name == "TUNER" {
             Ok(self.dev.get_center_freq() as f64)
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
@@ -343,30 +557,23 @@ impl DeviceTrait for RtlSdr {
         name: &str,
         frequency: f64,
     ) -> Result<(), Error> {
-        if matches!(direction, Rx)
-            && channel == 0
-            && self
-                .frequency_range(direction, channel)?
-                .contains(frequency)
+        self.validate_channel(direction, channel)?;
+        if self
+            .frequency_range(direction, channel)?
+            .contains(frequency)
             && name == "TUNER"
         {
-            self.dev.set_center_freq(frequency as u32)?;
+            let frequency = crate::device::round_hz(frequency, u32::MAX as u64)? as u32;
+            self.dev.set_center_freq(frequency)?;
             Ok(self.dev.reset_buffer()?)
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
     fn sample_rate(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok(self.dev.get_sample_rate() as f64)
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok(self.dev.get_sample_rate() as f64)
     }
 
     fn set_sample_rate(
@@ -375,64 +582,161 @@ impl DeviceTrait for RtlSdr {
         channel: usize,
         rate: f64,
     ) -> Result<(), Error> {
-        if matches!(direction, Rx)
-            && channel == 0
-            && self
-                .get_sample_rate_range(direction, channel)?
-                .contains(rate)
+        self.validate_channel(direction, channel)?;
+        if self
+            .get_sample_rate_range(direction, channel)?
+            .contains(rate)
         {
-            self.dev.set_tuner_bandwidth(rate as u32)?;
-            Ok(self.dev.set_sample_rate(rate as u32)?)
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
+            let rate = crate::device::round_hz(rate, u32::MAX as u64)? as u32;
+            self.dev.set_tuner_bandwidth(rate)?;
+            Ok(self.dev.set_sample_rate(rate)?)
         } else {
-            Err(Error::NotSupported)
+            Err(Error::ValueError)
         }
     }
 
     fn get_sample_rate_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
-        if matches!(direction, Rx) && channel == 0 {
-            Ok(Range::new(vec![
-                RangeItem::Interval(225_001.0, 300_000.0),
-                RangeItem::Interval(900_001.0, 3_200_000.0),
-            ]))
-        } else if matches!(direction, Rx) {
-            Err(Error::ValueError)
-        } else {
-            Err(Error::NotSupported)
-        }
+        self.validate_channel(direction, channel)?;
+        Ok(Range::new(vec![
+            RangeItem::Interval(225_001.0, 300_000.0),
+            RangeItem::Interval(900_001.0, 3_200_000.0),
+        ]))
     }
 }
 
 impl crate::RxStreamer for RxStreamer {
     fn mtu(&self) -> Result<usize, Error> {
-        Ok(MTU)
+        Ok(self.buf.len() / 2)
     }
-    fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
-        self.dev.reset_buffer().or(Err(Error::DeviceError))
+    fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        self.cancel.reset();
+        if self.active {
+            return Ok(());
+        }
+        if let Some(t) = time_ns {
+            log::warn!(
+                "{}: timed activation not supported by hardware, waiting on host",
+                self.log.channel(Rx, 0)
+            );
+            std::thread::sleep(Duration::from_nanos(t.max(0) as u64));
+        }
+        self.stats = StreamStats::default();
+        self.pending = 0..0;
+        self.dev.reset_buffer().or(Err(Error::DeviceError))?;
+        self.active = true;
+        Ok(())
     }
-    fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+    fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        if !self.active {
+            return Ok(());
+        }
+        if let Some(t) = time_ns {
+            log::warn!(
+                "{}: timed deactivation not supported by hardware, waiting on host",
+                self.log.channel(Rx, 0)
+            );
+            std::thread::sleep(Duration::from_nanos(t.max(0) as u64));
+        }
+        self.active = false;
         Ok(())
     }
     fn read(&mut self, buffers: &mut [&mut [Complex32]], _timeout_us: i64) -> Result<usize, Error> {
         debug_assert_eq!(buffers.len(), 1);
-        // make len multiple of 256 to make u multiple of 512
-        let len = std::cmp::min(buffers[0].len(), MTU / 2);
-        let len = len & !0xff;
-        if len == 0 {
+        if buffers[0].is_empty() {
             return Ok(0);
         }
-        let n = self.dev.read_sync(&mut self.buf[0..len * 2])?;
-        debug_assert_eq!(n % 2, 0);
+        if self.pending.is_empty() {
+            // make len multiple of 256 to make u multiple of 512, but transfer at least 256
+            // samples and keep what does not fit into the buffers
+            let len = std::cmp::min(buffers[0].len(), self.buf.len() / 2);
+            let len = std::cmp::max(len & !0xff, 0x100);
+            // the blocking USB transfer cannot be interrupted, so only check before starting it
+            if self.cancel.is_canceled() {
+                return Ok(0);
+            }
+            let n = self.dev.read_sync(&mut self.buf[0..len * 2])?;
+            debug_assert_eq!(n % 2, 0);
+            debug_assert!(n / 2 <= len && len <= self.mtu()?);
+            self.stats.samples += (n / 2) as u64;
+            self.pending = 0..n;
+        }
 
-        for i in 0..n / 2 {
+        let n = std::cmp::min(buffers[0].len(), self.pending.len() / 2);
+        let start = self.pending.start;
+        for i in 0..n {
             buffers[0][i] = Complex32::new(
-                (self.buf[i * 2] as f32 - 127.0) / 128.0,
-                (self.buf[i * 2 + 1] as f32 - 127.0) / 128.0,
+                (self.buf[start + i * 2] as f32 - 127.0) / 128.0,
+                (self.buf[start + i * 2 + 1] as f32 - 127.0) / 128.0,
             );
         }
+        self.pending.start += n * 2;
+        Ok(n)
+    }
+
+    fn read_raw(
+        &mut self,
+        buf: &mut [u8],
+        format: SampleFormat,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        if format != SampleFormat::CS8 {
+            let mut samples = std::mem::take(&mut self.scratch);
+            let len = std::cmp::min(buf.len() / format.bytes_per_sample(), samples.len());
+            let r = self.read(&mut [&mut samples[..len]], timeout_us);
+            if let Ok(n) = r {
+                crate::convert::to_bytes(&samples[..n], buf, format);
+            }
+            self.scratch = samples;
+            return r;
+        }
+
+        // native format is offset binary, so flipping the MSB gives two's complement
+        let len = std::cmp::min(buf.len(), self.buf.len()) & !0x1ff;
+        if len == 0 || !self.pending.is_empty() {
+            if buf.len() < 2 {
+                return Ok(0);
+            }
+            if self.pending.is_empty() {
+                if self.cancel.is_canceled() {
+                    return Ok(0);
+                }
+                let n = self.dev.read_sync(&mut self.buf[0..0x200])?;
+                self.stats.samples += (n / 2) as u64;
+                self.pending = 0..n;
+            }
+            let n = std::cmp::min(buf.len(), self.pending.len()) & !1;
+            for (b, p) in buf[..n].iter_mut().zip(&self.buf[self.pending.start..]) {
+                *b = p ^ 0x80;
+            }
+            self.pending.start += n;
+            return Ok(n / 2);
+        }
+        if self.cancel.is_canceled() {
+            return Ok(0);
+        }
+        let n = self.dev.read_sync(&mut buf[0..len])?;
+        for b in buf[0..n].iter_mut() {
+            *b ^= 0x80;
+        }
+        self.stats.samples += (n / 2) as u64;
         Ok(n / 2)
     }
+
+    fn stats(&self) -> Result<StreamStats, Error> {
+        Ok(self.stats)
+    }
+
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        Ok(self.cancel.clone())
+    }
+
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        Ok(self.config.clone())
+    }
+
+    fn sample_rate(&self) -> Result<f64, Error> {
+        Ok(self.dev.get_sample_rate() as f64)
+    }
 }
 
 impl crate::TxStreamer for TxDummy {
@@ -464,3 +768,52 @@ impl crate::TxStreamer for TxDummy {
         unreachable!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels() {
+        assert!(check_channel(1, 0).is_ok());
+        assert!(matches!(
+            check_channel(1, 5),
+            Err(Error::InvalidChannel(5, v)) if v == vec![0]
+        ));
+        assert!(matches!(check_channel(0, 0), Err(Error::NotSupported)));
+    }
+
+    #[test]
+    fn open_retry() {
+        let mut calls = 0;
+        let r = retry(2, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                Err(Error::Timeout(0))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(r.unwrap(), 3);
+
+        // errors that are returned immediately
+        let calls = |error: fn() -> Error| {
+            let mut calls = 0;
+            let r: Result<(), Error> = retry(2, Duration::ZERO, || {
+                calls += 1;
+                Err(error())
+            });
+            assert!(r.is_err());
+            calls
+        };
+        assert_eq!(calls(|| Error::NotFound), 1);
+        assert_eq!(calls(|| Error::Busy), 1);
+    }
+
+    #[test]
+    fn busy() {
+        // a device that is claimed, e.g., by the original of a `try_clone`, is reported as busy
+        let e = seify_rtlsdr::error::RtlsdrError::Usb(rusb::Error::Busy);
+        assert!(matches!(Error::from(e), Error::Busy));
+    }
+}
@@ -1,30 +1,43 @@
 //! Soapy SDR
 use num_complex::Complex32;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use crate::Args;
+use crate::Calibration;
+use crate::CalibrationEntry;
 use crate::DeviceTrait;
 use crate::Direction;
 use crate::Driver;
 use crate::Error;
 use crate::Range;
 use crate::RangeItem;
+use crate::SampleFormat;
+use crate::StreamStats;
 
 /// Soapy Device
+///
+/// The hardware info is queried once when the device is opened, see
+/// [`info_refresh`](DeviceTrait::info_refresh).
 #[derive(Clone)]
 pub struct Soapy {
     dev: soapysdr::Device,
     args: Args,
     index: usize,
+    info: Arc<Mutex<Args>>,
 }
 
 /// Soapy RX Streamer
 pub struct RxStreamer {
     streamer: soapysdr::RxStream<Complex32>,
+    stats: StreamStats,
+    active: bool,
 }
 
 /// Soapy TX Streamer
 pub struct TxStreamer {
     streamer: soapysdr::TxStream<Complex32>,
+    active: bool,
 }
 
 impl Soapy {
@@ -41,9 +54,9 @@ impl Soapy {
                 match a.get::<String>("driver") {
                     Ok(d) => {
                         a.set("soapy_driver", d);
-                        a.set("driver", "soapy")
+                        a.set("driver", Driver::Soapy.to_string())
                     }
-                    Err(_) => a.set("driver", "soapy"),
+                    Err(_) => a.set("driver", Driver::Soapy.to_string()),
                 };
                 a
             })
@@ -64,12 +77,22 @@ impl Soapy {
             args.remove("driver");
         }
 
+        let dev = soapysdr::Device::new(soapysdr::Args::try_from(args)?)?;
+        let info = Self::query_info(&dev, &orig_args)?;
         Ok(Self {
-            dev: soapysdr::Device::new(soapysdr::Args::try_from(args)?)?,
+            dev,
             args: orig_args,
             index,
+            info: Arc::new(Mutex::new(info)),
         })
     }
+
+    fn query_info(dev: &soapysdr::Device, args: &Args) -> Result<Args, Error> {
+        // device args take precedence, so that the info can be used to reopen the device
+        let mut info: Args = dev.hardware_info()?.into();
+        info.merge(args.clone());
+        Ok(info)
+    }
 }
 
 impl DeviceTrait for Soapy {
@@ -93,7 +116,13 @@ impl DeviceTrait for Soapy {
     }
 
     fn info(&self) -> Result<Args, Error> {
-        Ok(self.args.clone())
+        Ok(self.info.lock().unwrap().clone())
+    }
+
+    fn info_refresh(&self) -> Result<Args, Error> {
+        let info = Self::query_info(&self.dev, &self.args)?;
+        *self.info.lock().unwrap() = info.clone();
+        Ok(info)
     }
 
     fn num_channels(&self, direction: Direction) -> Result<usize, Error> {
@@ -104,22 +133,46 @@ impl DeviceTrait for Soapy {
         Ok(self.dev.full_duplex(direction.into(), channel)?)
     }
 
-    fn rx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::RxStreamer, Error> {
+    fn set_feature(&self, key: &str, value: &str) -> Result<(), Error> {
+        Ok(self.dev.write_setting(key, value)?)
+    }
+
+    fn feature(&self, key: &str) -> Result<String, Error> {
+        Ok(self.dev.read_setting(key)?)
+    }
+
+    fn rx_streamer(&self, channels: &[usize], mut args: Args) -> Result<Self::RxStreamer, Error> {
+        crate::device::stream_format(&args, &self.supported_formats(Direction::Rx)?)?;
+        args.remove("stream_format");
         Ok(RxStreamer {
             streamer: self
                 .dev
                 .rx_stream_args(channels, soapysdr::Args::try_from(args)?)?,
+            stats: StreamStats::default(),
+            active: false,
         })
     }
 
-    fn tx_streamer(&self, channels: &[usize], args: Args) -> Result<Self::TxStreamer, Error> {
+    fn tx_streamer(&self, channels: &[usize], mut args: Args) -> Result<Self::TxStreamer, Error> {
+        crate::device::stream_format(&args, &self.supported_formats(Direction::Tx)?)?;
+        args.remove("stream_format");
         Ok(TxStreamer {
             streamer: self
                 .dev
                 .tx_stream_args(channels, soapysdr::Args::try_from(args)?)?,
+            active: false,
         })
     }
 
+    fn supported_formats(&self, direction: Direction) -> Result<Vec<SampleFormat>, Error> {
+        Ok(self
+            .dev
+            .stream_formats(direction.into(), 0)?
+            .iter()
+            .filter_map(|f| f.to_string().parse().ok())
+            .collect())
+    }
+
     fn antennas(&self, direction: Direction, channel: usize) -> Result<Vec<String>, Error> {
         Ok(self.dev.antennas(direction.into(), channel)?)
     }
@@ -294,6 +347,108 @@ impl DeviceTrait for Soapy {
         let range = self.dev.get_sample_rate_range(direction.into(), channel)?;
         Ok(range.into())
     }
+
+    fn bandwidth(&self, direction: Direction, channel: usize) -> Result<f64, Error> {
+        Ok(self.dev.bandwidth(direction.into(), channel)?)
+    }
+
+    fn set_bandwidth(&self, direction: Direction, channel: usize, bw: f64) -> Result<(), Error> {
+        Ok(self.dev.set_bandwidth(direction.into(), channel, bw)?)
+    }
+
+    fn get_bandwidth_range(&self, direction: Direction, channel: usize) -> Result<Range, Error> {
+        let range = self.dev.bandwidth_range(direction.into(), channel)?;
+        Ok(range.into())
+    }
+
+    fn sensors(&self) -> Result<Vec<String>, Error> {
+        Ok(self.dev.list_sensors()?)
+    }
+
+    fn read_sensor(&self, key: &str) -> Result<String, Error> {
+        Ok(self.dev.read_sensor(key)?)
+    }
+
+    fn clock_sources(&self) -> Result<Vec<String>, Error> {
+        Ok(self.dev.list_clock_sources()?)
+    }
+
+    fn clock_source(&self) -> Result<String, Error> {
+        Ok(self.dev.get_clock_source()?)
+    }
+
+    fn set_clock_source(&self, source: &str) -> Result<(), Error> {
+        if !self.clock_sources()?.iter().any(|s| s == source) {
+            return Err(Error::ValueError);
+        }
+        Ok(self.dev.set_clock_source(source)?)
+    }
+
+    fn get_hardware_time(&self) -> Result<i64, Error> {
+        Ok(self.dev.get_hardware_time(None)?)
+    }
+
+    fn set_hardware_time(&self, time_ns: i64) -> Result<(), Error> {
+        Ok(self.dev.set_hardware_time(None, time_ns)?)
+    }
+
+    fn export_calibration(&self) -> Result<Vec<u8>, Error> {
+        let mut entries = Vec::new();
+        for direction in Direction::iter() {
+            for channel in 0..self.num_channels(direction)? {
+                let d = direction.into();
+                let dc_offset = if self.dev.has_dc_offset(d, channel)? {
+                    Some(self.dev.dc_offset(d, channel)?)
+                } else {
+                    None
+                };
+                let iq_balance = if self.dev.has_iq_balance(d, channel)? {
+                    Some(self.dev.iq_balance(d, channel)?)
+                } else {
+                    None
+                };
+                if dc_offset.is_some() || iq_balance.is_some() {
+                    entries.push(CalibrationEntry {
+                        direction,
+                        channel,
+                        dc_offset,
+                        iq_balance,
+                    });
+                }
+            }
+        }
+        Ok(Calibration { entries }.to_bytes())
+    }
+
+    fn import_calibration(&self, data: &[u8]) -> Result<(), Error> {
+        let cal = Calibration::from_bytes(data)?;
+        for e in cal.entries {
+            let d = e.direction.into();
+            if let Some((i, q)) = e.dc_offset {
+                self.dev.set_dc_offset(d, e.channel, i, q)?;
+            }
+            if let Some((i, q)) = e.iq_balance {
+                self.dev.set_iq_balance(d, e.channel, i, q)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RxStreamer {
+    fn read_counted(
+        &mut self,
+        buffers: &mut [&mut [num_complex::Complex32]],
+        timeout_us: i64,
+    ) -> Result<usize, soapysdr::Error> {
+        let r = self.streamer.read(buffers, timeout_us);
+        match &r {
+            Ok(n) => self.stats.samples += *n as u64,
+            Err(e) if e.code == soapysdr::ErrorCode::Overflow => self.stats.overruns += 1,
+            Err(_) => {}
+        }
+        r
+    }
 }
 
 impl crate::RxStreamer for RxStreamer {
@@ -302,11 +457,22 @@ impl crate::RxStreamer for RxStreamer {
     }
 
     fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        Ok(self.streamer.activate(time_ns)?)
+        if self.active {
+            return Ok(());
+        }
+        self.stats = StreamStats::default();
+        self.streamer.activate(time_ns)?;
+        self.active = true;
+        Ok(())
     }
 
     fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        Ok(self.streamer.deactivate(time_ns)?)
+        if !self.active {
+            return Ok(());
+        }
+        self.streamer.deactivate(time_ns)?;
+        self.active = false;
+        Ok(())
     }
 
     fn read(
@@ -314,7 +480,35 @@ impl crate::RxStreamer for RxStreamer {
         buffers: &mut [&mut [num_complex::Complex32]],
         timeout_us: i64,
     ) -> Result<usize, Error> {
-        Ok(self.streamer.read(buffers, timeout_us)?)
+        Ok(self.read_counted(buffers, timeout_us)?)
+    }
+
+    fn read_with_meta(
+        &mut self,
+        buffers: &mut [&mut [num_complex::Complex32]],
+        timeout_us: i64,
+    ) -> Result<(usize, crate::StreamMeta), Error> {
+        match self.read_counted(buffers, timeout_us) {
+            Ok(n) => Ok((
+                n,
+                crate::StreamMeta {
+                    time_ns: Some(self.streamer.time_ns()),
+                    ..Default::default()
+                },
+            )),
+            Err(e) if e.code == soapysdr::ErrorCode::Overflow => Ok((
+                0,
+                crate::StreamMeta {
+                    overflow: true,
+                    ..Default::default()
+                },
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn stats(&self) -> Result<StreamStats, Error> {
+        Ok(self.stats)
     }
 }
 
@@ -324,11 +518,21 @@ impl crate::TxStreamer for TxStreamer {
     }
 
     fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        Ok(self.streamer.activate(time_ns)?)
+        if self.active {
+            return Ok(());
+        }
+        self.streamer.activate(time_ns)?;
+        self.active = true;
+        Ok(())
     }
 
     fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
-        Ok(self.streamer.deactivate(time_ns)?)
+        if !self.active {
+            return Ok(());
+        }
+        self.streamer.deactivate(time_ns)?;
+        self.active = false;
+        Ok(())
     }
 
     fn write(
@@ -356,10 +560,10 @@ impl crate::TxStreamer for TxStreamer {
 
 impl From<soapysdr::Error> for Error {
     fn from(value: soapysdr::Error) -> Self {
-        if value.code == soapysdr::ErrorCode::Overflow {
-            Error::Overflow
-        } else {
-            Error::Soapy(value)
+        match value.code {
+            soapysdr::ErrorCode::Overflow => Error::Overflow,
+            soapysdr::ErrorCode::Underflow => Error::Underflow,
+            _ => Error::Soapy(value),
         }
     }
 }
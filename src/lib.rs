@@ -1,6 +1,30 @@
 mod args;
 pub use args::Args;
 
+#[cfg(feature = "async")]
+mod async_streamer;
+#[cfg(feature = "async")]
+pub use async_streamer::AsyncRxAdapter;
+#[cfg(feature = "async")]
+pub use async_streamer::AsyncRxStreamer;
+
+mod builder;
+pub use builder::DeviceBuilder;
+
+mod calibration;
+pub use calibration::Calibration;
+pub use calibration::CalibrationEntry;
+
+mod capabilities;
+pub use capabilities::Capabilities;
+pub use capabilities::ChannelCapabilities;
+
+mod config;
+pub use config::DeviceConfig;
+pub use config::Gain;
+
+pub mod convert;
+
 mod device;
 pub use device::Device;
 pub use device::DeviceTrait;
@@ -8,14 +32,49 @@ pub use device::GenericDevice;
 
 pub mod impls;
 
+mod logging;
+
+#[cfg(feature = "metrics")]
+mod metered;
+
+mod player;
+pub use player::Player;
+
 mod range;
 pub use range::Range;
 pub use range::RangeItem;
 
+mod recorder;
+pub use recorder::Recorder;
+
 mod streamer;
+pub use streamer::CancelToken;
 pub use streamer::RxStreamer;
+pub use streamer::SampleFormat;
+pub use streamer::StreamMeta;
+pub use streamer::StreamStats;
+pub use streamer::StreamerConfig;
+pub use streamer::Timeout;
 pub use streamer::TxStreamer;
 
+mod temperature;
+
+#[cfg(feature = "typed-units")]
+mod units;
+#[cfg(feature = "typed-units")]
+pub use units::Decibels;
+#[cfg(feature = "typed-units")]
+pub use units::Hz;
+#[cfg(feature = "typed-units")]
+pub use units::SamplesPerSec;
+
+mod watch;
+pub use watch::watch;
+pub use watch::watch_with_interval;
+pub use watch::DeviceEvent;
+pub use watch::Watcher;
+pub use watch::WATCH_INTERVAL;
+
 use serde::{Deserialize, Serialize};
 
 use std::str::FromStr;
@@ -30,6 +89,8 @@ pub enum Error {
     OutOfRange(Range, f64),
     #[error("Value Error")]
     ValueError,
+    #[error("Invalid channel {0}, valid channels: {1:?}")]
+    InvalidChannel(usize, Vec<usize>),
     #[error("Not Found")]
     NotFound,
     #[error("corresponding feature not enabled")]
@@ -38,10 +99,22 @@ pub enum Error {
     NotSupported,
     #[error("Overflow")]
     Overflow,
+    #[error("Underflow")]
+    Underflow,
     #[error("Inactive")]
     Inactive,
+    #[error("Busy")]
+    Busy,
+    #[error("Timeout ({0} samples transferred)")]
+    Timeout(usize),
+    #[error("Canceled ({0} samples transferred)")]
+    Canceled(usize),
+    #[error("Applying {0} failed ({1})")]
+    Apply(String, Box<Error>),
     #[error("Json ({0})")]
     Json(#[from] serde_json::Error),
+    #[error("No driver could open the device ({})", display_open_errors(.0))]
+    OpenFailed(Vec<(Driver, Error)>),
     #[error("Misc")]
     Misc(String),
     #[error("Io ({0})")]
@@ -54,7 +127,18 @@ pub enum Error {
     Ureq(Box<ureq::Error>),
     #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
     #[error("RtlSdr ({0})")]
-    RtlSdr(#[from] seify_rtlsdr::error::RtlsdrError),
+    RtlSdr(seify_rtlsdr::error::RtlsdrError),
+}
+
+impl Error {
+    /// Whether the operation might succeed if retried later, e.g., after a timeout or when a
+    /// busy device or channel becomes available.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::Timeout(_) | Error::Overflow | Error::Underflow | Error::Busy
+        )
+    }
 }
 
 #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
@@ -64,16 +148,52 @@ impl From<ureq::Error> for Error {
     }
 }
 
+#[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
+impl From<seify_rtlsdr::error::RtlsdrError> for Error {
+    fn from(value: seify_rtlsdr::error::RtlsdrError) -> Self {
+        match value {
+            seify_rtlsdr::error::RtlsdrError::Usb(rusb::Error::Busy) => Error::Busy,
+            e => Error::RtlSdr(e),
+        }
+    }
+}
+
+fn display_open_errors(errors: &[(Driver, Error)]) -> String {
+    errors
+        .iter()
+        .map(|(d, e)| format!("{d:?}: {e}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Supported hardware drivers.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Driver {
     Aaronia,
     AaroniaHttp,
+    File,
+    Mock,
+    Null,
     RtlSdr,
     Soapy,
 }
 
+impl std::fmt::Display for Driver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Driver::Aaronia => "aaronia",
+            Driver::AaroniaHttp => "aaronia_http",
+            Driver::File => "file",
+            Driver::Mock => "mock",
+            Driver::Null => "null",
+            Driver::RtlSdr => "rtlsdr",
+            Driver::Soapy => "soapy",
+        };
+        write!(f, "{s}")
+    }
+}
+
 impl FromStr for Driver {
     type Err = Error;
 
@@ -85,6 +205,15 @@ impl FromStr for Driver {
         if s == "aaronia_http" || s == "aaronia-http" || s == "aaroniahttp" {
             return Ok(Driver::AaroniaHttp);
         }
+        if s == "file" {
+            return Ok(Driver::File);
+        }
+        if s == "null" {
+            return Ok(Driver::Null);
+        }
+        if s == "mock" {
+            return Ok(Driver::Mock);
+        }
         if s == "rtlsdr" || s == "rtl-sdr" || s == "rtl" {
             return Ok(Driver::RtlSdr);
         }
@@ -102,6 +231,43 @@ pub enum Direction {
     Tx,
 }
 
+impl Direction {
+    /// Iterate over both directions, i.e., `Rx` and `Tx`.
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        [Direction::Rx, Direction::Tx].into_iter()
+    }
+
+    /// The other direction.
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Rx => Direction::Tx,
+            Direction::Tx => Direction::Rx,
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Direction::Rx => "rx",
+            Direction::Tx => "tx",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rx" => Ok(Direction::Rx),
+            "tx" => Ok(Direction::Tx),
+            _ => Err(Error::ValueError),
+        }
+    }
+}
+
 /// Enumerate devices.
 ///
 /// ## Returns
@@ -113,6 +279,44 @@ pub fn enumerate() -> Result<Vec<Args>, Error> {
     enumerate_with_args(Args::new())
 }
 
+/// Probe all enabled drivers for devices matching `args`.
+///
+/// Like [`enumerate_with_args`], but takes the [`Args`] by reference and removes duplicate
+/// entries. Every entry contains the `driver` that found the device, so that passing it to
+/// [`Device::from_args`](crate::Device::from_args) opens the device with this driver.
+pub fn probe(args: &Args) -> Result<Vec<Args>, Error> {
+    let mut devs: Vec<Args> = Vec::new();
+    for d in enumerate_with_args(args.clone())? {
+        debug_assert!(d.get::<String>("driver").is_ok());
+        if !devs.contains(&d) {
+            devs.push(d);
+        }
+    }
+    Ok(devs)
+}
+
+/// Drivers that are compiled into this build, depending on the enabled features and the target.
+#[allow(clippy::vec_init_then_push)]
+pub fn available_drivers() -> Vec<Driver> {
+    #[allow(unused_mut)]
+    let mut drivers = Vec::new();
+    #[cfg(all(feature = "aaronia", any(target_os = "linux", target_os = "windows")))]
+    drivers.push(Driver::Aaronia);
+    #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
+    drivers.push(Driver::AaroniaHttp);
+    #[cfg(feature = "file")]
+    drivers.push(Driver::File);
+    #[cfg(feature = "mock")]
+    drivers.push(Driver::Mock);
+    #[cfg(feature = "null")]
+    drivers.push(Driver::Null);
+    #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
+    drivers.push(Driver::RtlSdr);
+    #[cfg(all(feature = "soapy", not(target_arch = "wasm32")))]
+    drivers.push(Driver::Soapy);
+    drivers
+}
+
 /// Enumerate devices with given [`Args`].
 ///
 /// ## Returns
@@ -127,6 +331,9 @@ pub fn enumerate_with_args<A: TryInto<Args>>(a: A) -> Result<Vec<Args>, Error> {
         Ok(s) => Some(s.parse::<Driver>()?),
         Err(_) => None,
     };
+    if driver.is_some_and(|d| !available_drivers().contains(&d)) {
+        return Err(Error::FeatureNotEnabled);
+    }
 
     #[cfg(all(feature = "aaronia", any(target_os = "linux", target_os = "windows")))]
     {
@@ -134,12 +341,6 @@ pub fn enumerate_with_args<A: TryInto<Args>>(a: A) -> Result<Vec<Args>, Error> {
             devs.append(&mut impls::Aaronia::probe(&args)?)
         }
     }
-    #[cfg(not(all(feature = "aaronia", any(target_os = "linux", target_os = "windows"))))]
-    {
-        if matches!(driver, Some(Driver::Aaronia)) {
-            return Err(Error::FeatureNotEnabled);
-        }
-    }
 
     #[cfg(all(feature = "aaronia_http", not(target_arch = "wasm32")))]
     {
@@ -147,39 +348,111 @@ pub fn enumerate_with_args<A: TryInto<Args>>(a: A) -> Result<Vec<Args>, Error> {
             devs.append(&mut impls::AaroniaHttp::probe(&args)?)
         }
     }
-    #[cfg(not(all(feature = "aaronia_http", not(target_arch = "wasm32"))))]
+
+    #[cfg(feature = "file")]
     {
-        if matches!(driver, Some(Driver::AaroniaHttp)) {
-            return Err(Error::FeatureNotEnabled);
+        if matches!(driver, Some(Driver::File)) {
+            devs.append(&mut impls::FileDevice::probe(&args)?)
         }
     }
 
-    #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
+    #[cfg(feature = "null")]
     {
-        if driver.is_none() || matches!(driver, Some(Driver::RtlSdr)) {
-            devs.append(&mut impls::RtlSdr::probe(&args)?)
+        if matches!(driver, Some(Driver::Null)) {
+            devs.append(&mut impls::NullDevice::probe(&args)?)
         }
     }
-    #[cfg(not(all(feature = "rtlsdr", not(target_arch = "wasm32"))))]
+
+    #[cfg(feature = "mock")]
     {
-        if matches!(driver, Some(Driver::RtlSdr)) {
-            return Err(Error::FeatureNotEnabled);
+        if matches!(driver, Some(Driver::Mock)) {
+            devs.append(&mut impls::MockDevice::probe(&args)?)
         }
     }
 
-    #[cfg(all(feature = "soapy", not(target_arch = "wasm32")))]
+    #[cfg(all(feature = "rtlsdr", not(target_arch = "wasm32")))]
     {
-        if driver.is_none() || matches!(driver, Some(Driver::Soapy)) {
-            devs.append(&mut impls::Soapy::probe(&args)?)
+        if driver.is_none() || matches!(driver, Some(Driver::RtlSdr)) {
+            devs.append(&mut impls::RtlSdr::probe(&args)?)
         }
     }
-    #[cfg(not(all(feature = "soapy", not(target_arch = "wasm32"))))]
+
+    #[cfg(all(feature = "soapy", not(target_arch = "wasm32")))]
     {
-        if matches!(driver, Some(Driver::Soapy)) {
-            return Err(Error::FeatureNotEnabled);
+        if driver.is_none() || matches!(driver, Some(Driver::Soapy)) {
+            devs.append(&mut impls::Soapy::probe(&args)?)
         }
     }
 
     let _ = &mut devs;
     Ok(devs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRIVERS: [Driver; 7] = [
+        Driver::Aaronia,
+        Driver::AaroniaHttp,
+        Driver::File,
+        Driver::Mock,
+        Driver::Null,
+        Driver::RtlSdr,
+        Driver::Soapy,
+    ];
+
+    #[test]
+    fn driver_names() {
+        for d in DRIVERS {
+            assert_eq!(Driver::from_str(&d.to_string()).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction::iter() {
+            assert_eq!(Direction::from_str(&d.to_string()).unwrap(), d);
+            assert_ne!(d.opposite(), d);
+            assert_eq!(d.opposite().opposite(), d);
+        }
+        assert_eq!(Direction::from_str("TX").unwrap(), Direction::Tx);
+        assert!(Direction::from_str("both").is_err());
+    }
+
+    #[test]
+    fn available() {
+        let drivers = available_drivers();
+        assert_eq!(drivers.contains(&Driver::Mock), cfg!(feature = "mock"));
+        assert_eq!(
+            drivers.contains(&Driver::RtlSdr),
+            cfg!(all(feature = "rtlsdr", not(target_arch = "wasm32")))
+        );
+    }
+
+    #[test]
+    fn disabled_drivers() {
+        for d in DRIVERS {
+            if !available_drivers().contains(&d) {
+                let args = format!("driver={d}");
+                assert!(matches!(
+                    enumerate_with_args(args.as_str()),
+                    Err(Error::FeatureNotEnabled)
+                ));
+                assert!(matches!(
+                    Device::from_args(args.as_str()),
+                    Err(Error::FeatureNotEnabled)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn retryable() {
+        assert!(Error::Busy.is_retryable());
+        assert!(Error::Timeout(0).is_retryable());
+        assert!(Error::Underflow.is_retryable());
+        assert!(!Error::ValueError.is_retryable());
+        assert!(!Error::NotSupported.is_retryable());
+    }
+}
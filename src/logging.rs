@@ -0,0 +1,76 @@
+//! Consistent context for log messages of drivers.
+#![allow(dead_code)]
+use std::fmt;
+
+use crate::Direction;
+use crate::Driver;
+
+/// Identifies the device that logs a message, so that messages of multiple devices can be told
+/// apart.
+///
+/// Displays as key-value fields, e.g., `driver=rtlsdr serial=00000001`, and is meant to prefix
+/// log messages: `log::warn!("{}: gain out of range", self.log)`.
+#[derive(Clone, Debug)]
+pub(crate) struct LogContext {
+    driver: Driver,
+    key: &'static str,
+    id: String,
+}
+
+impl LogContext {
+    /// Context for the device of `driver` that is identified by the `key=id` field.
+    pub(crate) fn new(driver: Driver, key: &'static str, id: impl Into<String>) -> Self {
+        Self {
+            driver,
+            key,
+            id: id.into(),
+        }
+    }
+
+    /// Context for a `channel` of the device, adding direction and channel fields.
+    pub(crate) fn channel(&self, direction: Direction, channel: usize) -> ChannelContext<'_> {
+        ChannelContext {
+            device: self,
+            direction,
+            channel,
+        }
+    }
+}
+
+impl fmt::Display for LogContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "driver={} {}={}", self.driver, self.key, self.id)
+    }
+}
+
+/// [`LogContext`] of a channel, see [`LogContext::channel`].
+pub(crate) struct ChannelContext<'a> {
+    device: &'a LogContext,
+    direction: Direction,
+    channel: usize,
+}
+
+impl fmt::Display for ChannelContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} direction={} channel={}",
+            self.device, self.direction, self.channel
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        let l = LogContext::new(Driver::RtlSdr, "serial", "00000001");
+        assert_eq!(l.to_string(), "driver=rtlsdr serial=00000001");
+        assert_eq!(
+            l.channel(Direction::Rx, 0).to_string(),
+            "driver=rtlsdr serial=00000001 direction=rx channel=0"
+        );
+    }
+}
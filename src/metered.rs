@@ -0,0 +1,180 @@
+//! Stream metrics, recorded through the [`metrics`](https://docs.rs/metrics) facade.
+use metrics::counter;
+use metrics::Counter;
+use num_complex::Complex32;
+
+use crate::CancelToken;
+use crate::Driver;
+use crate::Error;
+use crate::RxStreamer;
+use crate::SampleFormat;
+use crate::StreamMeta;
+use crate::StreamStats;
+use crate::StreamerConfig;
+use crate::TxStreamer;
+
+struct Counters {
+    samples: Counter,
+    flow: Counter,
+    timeouts: Counter,
+}
+
+impl Counters {
+    fn new(driver: Driver, direction: &str) -> Self {
+        let driver = driver.to_string();
+        let flow = if direction == "rx" {
+            "seify_overflows_total"
+        } else {
+            "seify_underflows_total"
+        };
+        Self {
+            samples: counter!(format!("seify_{direction}_samples_total"), "driver" => driver.clone()),
+            flow: counter!(flow, "driver" => driver.clone()),
+            timeouts: counter!(format!("seify_{direction}_timeouts_total"), "driver" => driver),
+        }
+    }
+
+    /// Record the outcome of a stream operation, passing it on.
+    fn record<T>(
+        &self,
+        result: Result<T, Error>,
+        samples: impl Fn(&T) -> usize,
+    ) -> Result<T, Error> {
+        match &result {
+            Ok(t) => self.samples.increment(samples(t) as u64),
+            Err(Error::Timeout(n)) => {
+                self.samples.increment(*n as u64);
+                self.timeouts.increment(1);
+            }
+            Err(Error::Overflow | Error::Underflow) => self.flow.increment(1),
+            Err(_) => {}
+        }
+        result
+    }
+}
+
+/// Streamer that updates the metrics counters of the stream it wraps.
+///
+/// Counters are labeled with the `driver` and are named
+///  * `seify_rx_samples_total` / `seify_tx_samples_total`,
+///  * `seify_overflows_total` / `seify_underflows_total`,
+///  * `seify_rx_timeouts_total` / `seify_tx_timeouts_total`.
+pub(crate) struct Metered<S> {
+    inner: S,
+    counters: Counters,
+}
+
+impl<S: RxStreamer> Metered<S> {
+    pub(crate) fn rx(inner: S, driver: Driver) -> Self {
+        Self {
+            inner,
+            counters: Counters::new(driver, "rx"),
+        }
+    }
+}
+
+impl<S: TxStreamer> Metered<S> {
+    pub(crate) fn tx(inner: S, driver: Driver) -> Self {
+        Self {
+            inner,
+            counters: Counters::new(driver, "tx"),
+        }
+    }
+}
+
+// `read_exact` is not forwarded, so that its default implementation records the individual reads.
+impl<S: RxStreamer> RxStreamer for Metered<S> {
+    fn mtu(&self) -> Result<usize, Error> {
+        self.inner.mtu()
+    }
+    fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        self.inner.activate_at(time_ns)
+    }
+    fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        self.inner.deactivate_at(time_ns)
+    }
+    fn read(&mut self, buffers: &mut [&mut [Complex32]], timeout_us: i64) -> Result<usize, Error> {
+        let r = self.inner.read(buffers, timeout_us);
+        self.counters.record(r, |n| *n)
+    }
+    fn samples_available(&self) -> Result<usize, Error> {
+        self.inner.samples_available()
+    }
+    fn sample_rate(&self) -> Result<f64, Error> {
+        self.inner.sample_rate()
+    }
+    fn read_raw(
+        &mut self,
+        buf: &mut [u8],
+        format: SampleFormat,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        let r = self.inner.read_raw(buf, format, timeout_us);
+        self.counters.record(r, |n| *n)
+    }
+    fn read_with_meta(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<(usize, StreamMeta), Error> {
+        let r = self.inner.read_with_meta(buffers, timeout_us);
+        if let Ok((_, meta)) = &r {
+            if meta.overflow {
+                self.counters.flow.increment(1);
+            }
+        }
+        self.counters.record(r, |(n, _)| *n)
+    }
+    fn stats(&self) -> Result<StreamStats, Error> {
+        self.inner.stats()
+    }
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        self.inner.config()
+    }
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        self.inner.cancel_token()
+    }
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        self.inner.raw_fd()
+    }
+}
+
+impl<S: TxStreamer> TxStreamer for Metered<S> {
+    fn mtu(&self) -> Result<usize, Error> {
+        self.inner.mtu()
+    }
+    fn activate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        self.inner.activate_at(time_ns)
+    }
+    fn deactivate_at(&mut self, time_ns: Option<i64>) -> Result<(), Error> {
+        self.inner.deactivate_at(time_ns)
+    }
+    fn write(
+        &mut self,
+        buffers: &[&[Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        let r = self.inner.write(buffers, at_ns, end_burst, timeout_us);
+        self.counters.record(r, |n| *n)
+    }
+    fn write_all(
+        &mut self,
+        buffers: &[&[Complex32]],
+        at_ns: Option<i64>,
+        end_burst: bool,
+        timeout_us: i64,
+    ) -> Result<(), Error> {
+        let len = buffers.first().map_or(0, |b| b.len());
+        let r = self.inner.write_all(buffers, at_ns, end_burst, timeout_us);
+        self.counters.record(r, |_| len)
+    }
+    fn drain(&mut self, timeout_us: i64) -> Result<(), Error> {
+        self.inner.drain(timeout_us)
+    }
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        self.inner.config()
+    }
+}
@@ -0,0 +1,201 @@
+use num_complex::Complex32;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::Error;
+use crate::StreamStats;
+use crate::TxStreamer;
+
+/// Time that samples are written ahead of their due time.
+const LEAD: Duration = Duration::from_millis(20);
+/// Duration of the zeros that are transmitted on an underflow.
+const PADDING: Duration = Duration::from_millis(10);
+/// Timeout of the writes of the background thread.
+const WRITE_TIMEOUT_US: i64 = 1_000_000;
+
+#[derive(Default)]
+struct Stats {
+    samples: AtomicU64,
+    underflows: AtomicU64,
+    padded: AtomicU64,
+}
+
+/// Continuous player, writing to a [`TxStreamer`] on a background thread.
+///
+/// The application fills a ring of [`mtu`](TxStreamer::mtu)-sized buffers with
+/// [`push`](Player::push), which the thread writes to the first channel of the stream, paced to
+/// the sample rate. If no samples are queued when they are due, the thread transmits zeros and
+/// counts an underflow in the [`stats`](Player::stats).
+pub struct Player<S: TxStreamer + 'static> {
+    filled: mpsc::SyncSender<(Vec<Complex32>, usize)>,
+    free: mpsc::Receiver<Vec<Complex32>>,
+    stats: Arc<Stats>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Result<S, Error>>>,
+}
+
+impl<S: TxStreamer + 'static> Player<S> {
+    /// Activate the `streamer` and start playing from a ring of `buffers` buffers at
+    /// `sample_rate`.
+    pub fn new(mut streamer: S, sample_rate: f64, buffers: usize) -> Result<Self, Error> {
+        if buffers == 0 || !sample_rate.is_finite() || sample_rate <= 0.0 {
+            return Err(Error::ValueError);
+        }
+        let mtu = streamer.mtu()?;
+        if mtu == 0 {
+            return Err(Error::ValueError);
+        }
+        let (free_tx, free) = mpsc::sync_channel(buffers);
+        let (filled, filled_rx) = mpsc::sync_channel::<(Vec<Complex32>, usize)>(buffers);
+        for _ in 0..buffers {
+            free_tx.send(vec![Complex32::new(0.0, 0.0); mtu]).unwrap();
+        }
+
+        streamer.activate()?;
+        let stats = Arc::new(Stats::default());
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::spawn({
+            let stats = stats.clone();
+            let stop = stop.clone();
+            move || {
+                let padding = ((PADDING.as_secs_f64() * sample_rate) as usize).clamp(1, mtu);
+                let zeros = vec![Complex32::new(0.0, 0.0); padding];
+                let start = Instant::now();
+                let mut sent = 0u64;
+                loop {
+                    let due = start + Duration::from_secs_f64(sent as f64 / sample_rate);
+                    let now = Instant::now();
+                    if due > now + LEAD {
+                        std::thread::sleep(due - now - LEAD);
+                    }
+                    let r = match filled_rx.try_recv() {
+                        Ok((buf, len)) => {
+                            let r =
+                                streamer.write_all(&[&buf[..len]], None, false, WRITE_TIMEOUT_US);
+                            stats.samples.fetch_add(len as u64, Ordering::Relaxed);
+                            sent += len as u64;
+                            let _ = free_tx.send(buf);
+                            r
+                        }
+                        Err(mpsc::TryRecvError::Empty) if !stop.load(Ordering::Relaxed) => {
+                            stats.underflows.fetch_add(1, Ordering::Relaxed);
+                            stats.padded.fetch_add(padding as u64, Ordering::Relaxed);
+                            sent += padding as u64;
+                            streamer.write_all(&[&zeros], None, false, WRITE_TIMEOUT_US)
+                        }
+                        Err(_) => break,
+                    };
+                    if let Err(e) = r {
+                        let _ = streamer.deactivate();
+                        return Err(e);
+                    }
+                }
+                streamer.deactivate()?;
+                Ok(streamer)
+            }
+        });
+
+        Ok(Self {
+            filled,
+            free,
+            stats,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Queue `samples` for transmission, without blocking.
+    ///
+    /// Returns the number of samples queued, which is smaller than the length of `samples` if the
+    /// ring is full. If a write fails, the playback ends and the error is returned by
+    /// [`stop`](Player::stop).
+    pub fn push(&mut self, samples: &[Complex32]) -> usize {
+        let mut n = 0;
+        while n < samples.len() {
+            let Ok(mut buf) = self.free.try_recv() else {
+                break;
+            };
+            let k = std::cmp::min(samples.len() - n, buf.len());
+            buf[..k].copy_from_slice(&samples[n..n + k]);
+            if self.filled.send((buf, k)).is_err() {
+                break;
+            }
+            n += k;
+        }
+        n
+    }
+
+    /// Statistics of the playback, where underflows are counted if the ring was empty.
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            samples: self.stats.samples.load(Ordering::Relaxed),
+            underflows: self.stats.underflows.load(Ordering::Relaxed),
+            padded: self.stats.padded.load(Ordering::Relaxed),
+            ..Default::default()
+        }
+    }
+
+    /// Transmit the queued samples, stop the background thread, deactivate the streamer, and
+    /// return it.
+    ///
+    /// Returns the error that stopped the playback early, if any.
+    pub fn stop(mut self) -> Result<S, Error> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<S, Error> {
+        self.stop.store(true, Ordering::Relaxed);
+        match self.thread.take() {
+            Some(t) => t
+                .join()
+                .map_err(|_| Error::Misc("player thread panicked".to_string()))?,
+            None => Err(Error::Inactive),
+        }
+    }
+}
+
+impl<S: TxStreamer + 'static> Drop for Player<S> {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::impls::MockDevice;
+    use crate::Args;
+    use crate::DeviceTrait;
+
+    #[test]
+    fn play() {
+        let dev = MockDevice::open("").unwrap();
+        let tx = dev.tx_streamer(&[0], Args::new()).unwrap();
+        let mut player = Player::new(tx, 1e6, 4).unwrap();
+
+        let buf = vec![Complex32::new(1.0, 0.0); 1000];
+        assert_eq!(player.push(&buf), buf.len());
+        while player.stats().samples < buf.len() as u64 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        player.stop().unwrap();
+        assert!(dev.transmitted() >= buf.len());
+    }
+
+    #[test]
+    fn invalid() {
+        let dev = MockDevice::open("").unwrap();
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let tx = dev.tx_streamer(&[0], Args::new()).unwrap();
+            assert!(matches!(Player::new(tx, rate, 4), Err(Error::ValueError)));
+        }
+    }
+}
@@ -214,6 +214,28 @@ impl Range {
             close
         }
     }
+    /// Returns the smallest value in the [`Range`] or `None`, if the [`Range`] is empty.
+    pub fn min(&self) -> Option<f64> {
+        self.items
+            .iter()
+            .map(|i| match *i {
+                RangeItem::Interval(a, _) => a,
+                RangeItem::Value(v) => v,
+                RangeItem::Step(min, _, _) => min,
+            })
+            .reduce(f64::min)
+    }
+    /// Returns the largest value in the [`Range`] or `None`, if the [`Range`] is empty.
+    pub fn max(&self) -> Option<f64> {
+        self.items
+            .iter()
+            .map(|i| match *i {
+                RangeItem::Interval(_, b) => b,
+                RangeItem::Value(v) => v,
+                RangeItem::Step(min, max, step) => min + ((max - min) / step).floor() * step,
+            })
+            .reduce(f64::max)
+    }
     /// Merges two [`Ranges`](Range).
     pub fn merge(&mut self, mut r: Range) {
         self.items.append(&mut r.items)
@@ -292,4 +314,19 @@ mod tests {
         assert_eq!(r.at_max(100.3), Some(100.0));
         assert_eq!(r.at_max(111.3), Some(110.0));
     }
+    #[test]
+    fn min_max() {
+        let r = Range::new(Vec::new());
+        assert_eq!(r.min(), None);
+        assert_eq!(r.max(), None);
+        let r = Range::new(vec![
+            RangeItem::Value(123.0),
+            RangeItem::Interval(23.0, 42.0),
+            RangeItem::Step(100.0, 110.5, 1.0),
+        ]);
+        assert_eq!(r.min(), Some(23.0));
+        assert_eq!(r.max(), Some(123.0));
+        let r = Range::new(vec![RangeItem::Step(0.0, 10.5, 2.0)]);
+        assert_eq!(r.max(), Some(10.0));
+    }
 }
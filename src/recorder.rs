@@ -0,0 +1,204 @@
+use num_complex::Complex32;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use crate::CancelToken;
+use crate::Error;
+use crate::RxStreamer;
+use crate::StreamStats;
+
+/// Timeout of the reads of the background thread, bounding the time it takes to stop if the
+/// streamer does not support a [`CancelToken`].
+const READ_TIMEOUT_US: i64 = 100_000;
+
+#[derive(Default)]
+struct Stats {
+    samples: AtomicU64,
+    overruns: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Continuous recorder, reading from an [`RxStreamer`] on a background thread.
+///
+/// The thread reads the first channel of the stream into a ring of
+/// [`mtu`](RxStreamer::mtu)-sized buffers, which the application consumes with
+/// [`pop`](Recorder::pop). This decouples servicing the device from processing the samples, so
+/// that a briefly stalled consumer does not overflow the device. If all buffers are full, the
+/// thread drops the samples it reads and counts an overrun in the [`stats`](Recorder::stats).
+pub struct Recorder<S: RxStreamer + 'static> {
+    filled: mpsc::Receiver<(Vec<Complex32>, usize)>,
+    free: mpsc::SyncSender<Vec<Complex32>>,
+    current: Option<(Vec<Complex32>, usize, usize)>,
+    stats: Arc<Stats>,
+    stop: Arc<AtomicBool>,
+    cancel: Option<CancelToken>,
+    thread: Option<JoinHandle<Result<S, Error>>>,
+}
+
+impl<S: RxStreamer + 'static> Recorder<S> {
+    /// Activate the `streamer` and start recording into a ring of `buffers` buffers.
+    pub fn new(mut streamer: S, buffers: usize) -> Result<Self, Error> {
+        if buffers == 0 {
+            return Err(Error::ValueError);
+        }
+        let mtu = streamer.mtu()?;
+        let (free, free_rx) = mpsc::sync_channel(buffers);
+        let (filled_tx, filled) = mpsc::sync_channel(buffers);
+        for _ in 0..buffers {
+            free.send(vec![Complex32::new(0.0, 0.0); mtu]).unwrap();
+        }
+
+        streamer.activate()?;
+        let cancel = streamer.cancel_token().ok();
+        let stats = Arc::new(Stats::default());
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::spawn({
+            let stats = stats.clone();
+            let stop = stop.clone();
+            move || {
+                let mut spare = vec![Complex32::new(0.0, 0.0); mtu];
+                while !stop.load(Ordering::Relaxed) {
+                    let buf = free_rx.try_recv().ok();
+                    let full = buf.is_none();
+                    let mut buf = buf.unwrap_or_else(|| std::mem::take(&mut spare));
+                    match streamer.read(&mut [&mut buf], READ_TIMEOUT_US) {
+                        Ok(n) if full => {
+                            stats.overruns.fetch_add(1, Ordering::Relaxed);
+                            stats.dropped.fetch_add(n as u64, Ordering::Relaxed);
+                            spare = buf;
+                        }
+                        Ok(n) => {
+                            stats.samples.fetch_add(n as u64, Ordering::Relaxed);
+                            // there are only as many buffers as the channel has slots
+                            let _ = filled_tx.send((buf, n));
+                        }
+                        Err(e) => {
+                            match e {
+                                Error::Timeout(_) => {}
+                                Error::Overflow => {
+                                    stats.overruns.fetch_add(1, Ordering::Relaxed);
+                                }
+                                e => {
+                                    let _ = streamer.deactivate();
+                                    return Err(e);
+                                }
+                            }
+                            if full {
+                                spare = buf;
+                            } else {
+                                let _ = filled_tx.send((buf, 0));
+                            }
+                        }
+                    }
+                }
+                streamer.deactivate()?;
+                Ok(streamer)
+            }
+        });
+
+        Ok(Self {
+            filled,
+            free,
+            current: None,
+            stats,
+            stop,
+            cancel,
+            thread: Some(thread),
+        })
+    }
+
+    /// Copy recorded samples to `out`, without blocking.
+    ///
+    /// Returns the number of samples copied, which is zero if no samples are available. If a read
+    /// fails, the recording ends and the error is returned by [`stop`](Recorder::stop).
+    pub fn pop(&mut self, out: &mut [Complex32]) -> usize {
+        let mut n = 0;
+        while n < out.len() {
+            let (buf, len, pos) = match self.current.take() {
+                Some(c) => c,
+                None => match self.filled.try_recv() {
+                    Ok((buf, len)) => (buf, len, 0),
+                    Err(_) => break,
+                },
+            };
+            let k = std::cmp::min(out.len() - n, len - pos);
+            out[n..n + k].copy_from_slice(&buf[pos..pos + k]);
+            n += k;
+            if pos + k < len {
+                self.current = Some((buf, len, pos + k));
+            } else {
+                let _ = self.free.send(buf);
+            }
+        }
+        n
+    }
+
+    /// Statistics of the recording, where overruns are counted if the ring was full.
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            samples: self.stats.samples.load(Ordering::Relaxed),
+            overruns: self.stats.overruns.load(Ordering::Relaxed),
+            dropped: self.stats.dropped.load(Ordering::Relaxed),
+            ..Default::default()
+        }
+    }
+
+    /// Stop the background thread, deactivate the streamer, and return it.
+    ///
+    /// Returns the error that stopped the recording early, if any.
+    pub fn stop(mut self) -> Result<S, Error> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<S, Error> {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(c) = &self.cancel {
+            c.cancel();
+        }
+        match self.thread.take() {
+            Some(t) => t
+                .join()
+                .map_err(|_| Error::Misc("recorder thread panicked".to_string()))?,
+            None => Err(Error::Inactive),
+        }
+    }
+}
+
+impl<S: RxStreamer + 'static> Drop for Recorder<S> {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::Device;
+
+    #[test]
+    fn record() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        let rx = dev.rx_streamer(&[0]).unwrap();
+        let mut recorder = Recorder::new(rx, 4).unwrap();
+
+        let mut buf = vec![Complex32::new(0.0, 0.0); 1000];
+        let mut n = 0;
+        while n < buf.len() {
+            n += recorder.pop(&mut buf[n..]);
+        }
+        assert!(buf.iter().all(|s| s.norm() > 0.0));
+        assert!(recorder.stats().samples >= n as u64);
+
+        let mut rx = recorder.stop().unwrap();
+        let mut buf = [Complex32::new(0.0, 0.0); 1];
+        assert!(matches!(rx.read(&mut [&mut buf], 0), Err(Error::Inactive)));
+        drop(rx);
+        assert!(dev.rx_streamer(&[0]).is_ok());
+    }
+}
@@ -1,7 +1,190 @@
 use num_complex::Complex32;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::Error;
 
+/// Time that [`RxStreamer::read_exact`] waits before retrying a read that returned no samples.
+const EMPTY_READ_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Format of samples in raw stream buffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SampleFormat {
+    /// Complex 32-bit float, interleaved I/Q, normalized to `-1.0..1.0`.
+    CF32,
+    /// Complex 16-bit signed integer, interleaved I/Q.
+    CS16,
+    /// Complex 8-bit signed integer, interleaved I/Q.
+    CS8,
+}
+
+impl FromStr for SampleFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "CF32" => Ok(SampleFormat::CF32),
+            "CS16" => Ok(SampleFormat::CS16),
+            "CS8" => Ok(SampleFormat::CS8),
+            _ => Err(Error::ValueError),
+        }
+    }
+}
+
+impl std::fmt::Display for SampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SampleFormat::CF32 => "CF32",
+            SampleFormat::CS16 => "CS16",
+            SampleFormat::CS8 => "CS8",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl SampleFormat {
+    /// Size of one complex sample in bytes.
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            SampleFormat::CF32 => 8,
+            SampleFormat::CS16 => 4,
+            SampleFormat::CS8 => 2,
+        }
+    }
+}
+
+/// Metadata about a stream operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamMeta {
+    /// Hardware timestamp of the first sample in nanoseconds, if reported by the driver.
+    pub time_ns: Option<i64>,
+    /// Whether the read ended at a burst boundary.
+    pub end_of_burst: bool,
+    /// Whether samples were dropped before this read.
+    pub overflow: bool,
+}
+
+/// Timeout of a stream operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timeout {
+    /// Timeout in microseconds.
+    Us(i64),
+    /// Twice the time that the buffers take to fill at the sample rate of the stream.
+    Auto,
+    /// Wait until the operation completes.
+    Infinite,
+}
+
+impl Timeout {
+    /// Timeout in microseconds for reading `samples` at `sample_rate`.
+    ///
+    /// The sample rate is only required for [`Timeout::Auto`].
+    pub fn us(
+        &self,
+        samples: usize,
+        sample_rate: impl FnOnce() -> Result<f64, Error>,
+    ) -> Result<i64, Error> {
+        match self {
+            Timeout::Us(us) => Ok(*us),
+            Timeout::Auto => Ok((2e6 * samples as f64 / sample_rate()?).ceil() as i64),
+            Timeout::Infinite => Ok(i64::MAX),
+        }
+    }
+}
+
+impl From<i64> for Timeout {
+    fn from(us: i64) -> Self {
+        Timeout::Us(us)
+    }
+}
+
+impl From<Duration> for Timeout {
+    fn from(d: Duration) -> Self {
+        Timeout::Us(d.as_micros().try_into().unwrap_or(i64::MAX))
+    }
+}
+
+/// Handle to cancel the reads of an [`RxStreamer`] from another thread, see
+/// [`RxStreamer::cancel_token`].
+///
+/// Once canceled, reads return early with the samples read so far, until the stream is
+/// activated again.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    canceled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Create a token that is not canceled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Cancel the current and future reads until the stream is activated again.
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::SeqCst);
+    }
+    /// Whether the reads are canceled.
+    pub fn is_canceled(&self) -> bool {
+        self.canceled.load(Ordering::SeqCst)
+    }
+    // only used by the drivers that support cancellation
+    #[allow(dead_code)]
+    pub(crate) fn reset(&self) {
+        self.canceled.store(false, Ordering::SeqCst);
+    }
+    /// Sleep until `deadline` in short steps, returning `false` early if canceled.
+    #[allow(dead_code)]
+    pub(crate) fn sleep_until(&self, deadline: Instant) -> bool {
+        const STEP: Duration = Duration::from_millis(10);
+        loop {
+            if self.is_canceled() {
+                return false;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return true;
+            }
+            std::thread::sleep(remaining.min(STEP));
+        }
+    }
+}
+
+/// Cumulative statistics of a stream since its last activation.
+///
+/// Counts that a driver cannot observe or that do not apply to the direction of the stream stay
+/// zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Number of samples transferred between host and device.
+    pub samples: u64,
+    /// Number of RX overruns, i.e., of times that the host did not read fast enough.
+    pub overruns: u64,
+    /// Number of RX samples that were dropped by overruns.
+    pub dropped: u64,
+    /// Number of TX underflows, i.e., of times that the host did not provide samples in time.
+    pub underflows: u64,
+    /// Number of zero samples that were transmitted in place of missing samples.
+    pub padded: u64,
+}
+
+/// Configuration that a streamer was created with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamerConfig {
+    /// Size of the buffers of the driver, in samples.
+    pub buffer_size: usize,
+    /// Number of USB transfers in flight, if the driver queues them.
+    pub num_transfers: Option<usize>,
+    /// Channels of the stream.
+    pub channels: Vec<usize>,
+    /// Format of the samples on the wire.
+    pub format: SampleFormat,
+}
+
 /// Receive samples from a [Device](crate::Device) through one or multiple channels.
 pub trait RxStreamer: Send {
     /// Get the stream's maximum transmission unit (MTU) in number of elements.
@@ -9,6 +192,7 @@ pub trait RxStreamer: Send {
     /// The MTU specifies the maximum payload transfer in a stream operation.
     /// This value can be used as a stream buffer allocation size that can
     /// best optimize throughput given the underlying stream implementation.
+    /// It can be queried right after the streamer is created, before activating it.
     fn mtu(&self) -> Result<usize, Error>;
 
     /// Activate a stream.
@@ -22,6 +206,10 @@ pub trait RxStreamer: Send {
     ///
     /// Call `activate` to enable a stream before using `read()`
     ///
+    /// Drivers that cannot schedule the activation in hardware fall back to waiting on the host
+    /// and log a warning. `None` activates the stream immediately. Activating an active stream is
+    /// a no-op.
+    ///
     /// # Arguments:
     ///   * `time_ns` -- optional activation time in nanoseconds from the time the function is
     ///   called.
@@ -34,7 +222,8 @@ pub trait RxStreamer: Send {
     }
 
     /// Deactivate a stream.
-    /// The implementation will control switches or halt data flow.
+    /// The implementation will control switches or halt data flow. Deactivating an inactive
+    /// stream is a no-op.
     ///
     /// # Arguments:
     ///   * `time_ns` -- optional deactivation time in nanoseconds from the time the function is
@@ -46,11 +235,183 @@ pub trait RxStreamer: Send {
     /// `buffers` contains one destination slice for each channel of this stream.
     ///
     /// Returns the number of samples read, which may be smaller than the size of the passed arrays.
+    /// A single call never reads more than [`mtu`](RxStreamer::mtu) samples per channel, so larger
+    /// buffers are only partially filled.
     ///
     /// # Panics
     ///  * If `buffers` is not the same length as the `channels` array passed to
     ///  [`Device::rx_streamer`](crate::Device::rx_streamer) that created the streamer.
     fn read(&mut self, buffers: &mut [&mut [Complex32]], timeout_us: i64) -> Result<usize, Error>;
+
+    /// Works like [read](RxStreamer::read), but takes a [`Timeout`].
+    ///
+    /// With [`Timeout::Auto`], the timeout is twice the time that the buffers, clipped to the
+    /// [`mtu`](RxStreamer::mtu), take to fill at the [sample rate](RxStreamer::sample_rate) of the
+    /// stream.
+    fn read_with_timeout(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout: Timeout,
+    ) -> Result<usize, Error> {
+        let len = buffers.iter().map(|b| b.len()).min().unwrap_or(0);
+        let timeout_us = timeout.us(len.min(self.mtu()?), || self.sample_rate())?;
+        self.read(buffers, timeout_us)
+    }
+
+    /// Sample rate of the stream, which is used to derive [`Timeout::Auto`].
+    fn sample_rate(&self) -> Result<f64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Number of samples that can be read without blocking.
+    ///
+    /// The returned count is a lower bound, i.e., more samples might be available.
+    fn samples_available(&self) -> Result<usize, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Read samples of the first channel into a raw byte buffer, using the given `format`.
+    ///
+    /// Samples are stored interleaved (I/Q) in native byte order. Returns the number of samples
+    /// (not bytes) read. Drivers may implement their native format without conversion. The
+    /// default implementation reads [`Complex32`] samples and converts them, allocating a
+    /// temporary buffer on every call.
+    fn read_raw(
+        &mut self,
+        buf: &mut [u8],
+        format: SampleFormat,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        let mut samples = vec![Complex32::new(0.0, 0.0); buf.len() / format.bytes_per_sample()];
+        let n = self.read(&mut [&mut samples], timeout_us)?;
+        crate::convert::to_bytes(&samples[..n], buf, format);
+        Ok(n)
+    }
+
+    /// Read samples from the stream into the provided buffers, together with metadata.
+    ///
+    /// Works like [read](RxStreamer::read), but also returns a [`StreamMeta`] with the timestamp
+    /// of the first sample and the stream flags of the read, as far as the driver reports them.
+    /// The default implementation calls [read](RxStreamer::read) and returns empty metadata.
+    fn read_with_meta(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<(usize, StreamMeta), Error> {
+        Ok((self.read(buffers, timeout_us)?, StreamMeta::default()))
+    }
+
+    /// Statistics of the stream since it was last [activated](RxStreamer::activate).
+    ///
+    /// Helps to tell whether samples of a lossy capture were dropped. Drivers that do not keep
+    /// statistics return [`Error::NotSupported`].
+    fn stats(&self) -> Result<StreamStats, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Configuration that the streamer was created with, e.g., to log the effective buffer
+    /// setup of a capture. Returns [`Error::NotSupported`] if the driver does not track it.
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Token to cancel reads from another thread, e.g., to shut down a recorder on Ctrl-C
+    /// without waiting for a long timeout.
+    ///
+    /// A canceled [`read`](RxStreamer::read) returns early with the samples read so far, which
+    /// can be zero. Drivers that block in a transfer that cannot be interrupted return after it
+    /// completed. Drivers that do not support cancellation return [`Error::NotSupported`].
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// File descriptor that becomes readable when samples are available, to integrate the
+    /// stream into an event loop like `mio` or `epoll`.
+    ///
+    /// Only available on Unix platforms, and only if the driver exposes such a descriptor, e.g.,
+    /// the completion descriptor of its USB transfers. Returns `None` otherwise, which is the
+    /// default. The descriptor is owned by the streamer and must not be closed.
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        None
+    }
+
+    /// Read samples from the stream until the provided buffers are full.
+    ///
+    /// This method repeatedly calls [read](RxStreamer::read) until all buffers have been filled.
+    /// `timeout_us` applies to the whole operation, not to the individual reads.
+    ///
+    /// Returns [`Error::Timeout`] with the number of samples read so far if the buffers could not
+    /// be filled in time, or [`Error::Canceled`] if the reads were canceled through the
+    /// [`cancel_token`](RxStreamer::cancel_token).
+    ///
+    /// # Panics
+    ///  * If `buffers` is not the same length as the `channels` array passed to
+    ///    [`Device::rx_streamer`](crate::Device::rx_streamer) that created the streamer.
+    fn read_exact(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<(), Error> {
+        let deadline = Instant::now() + Duration::from_micros(timeout_us.max(0) as u64);
+        let len = buffers.iter().map(|b| b.len()).min().unwrap_or(0);
+        let token = self.cancel_token().ok();
+        let mut n = 0;
+
+        while n < len {
+            if token.as_ref().is_some_and(|t| t.is_canceled()) {
+                return Err(Error::Canceled(n));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::Timeout(n));
+            }
+            let mut bufs: Vec<&mut [Complex32]> = buffers.iter_mut().map(|b| &mut b[n..]).collect();
+            match self.read(&mut bufs, remaining.as_micros() as i64) {
+                Ok(0) => std::thread::sleep(remaining.min(EMPTY_READ_BACKOFF)),
+                Ok(k) => n += k,
+                Err(Error::Timeout(_)) => return Err(Error::Timeout(n)),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read up to `max` samples of a single-channel stream into a new `Vec`.
+    ///
+    /// Convenient for scripts and quick captures. The `Vec` is allocated with `max` samples up
+    /// front and filled with reads of at most [`mtu`](RxStreamer::mtu) samples each. It is
+    /// truncated to the samples read if `timeout_us` expires or the stream is
+    /// [canceled](RxStreamer::cancel_token) before it is full. To read continuously, reuse a
+    /// buffer with [`read`](RxStreamer::read) instead, which does not allocate.
+    fn read_to_vec(&mut self, max: usize, timeout_us: i64) -> Result<Vec<Complex32>, Error> {
+        let deadline = Instant::now() + Duration::from_micros(timeout_us.max(0) as u64);
+        let mtu = self.mtu()?.max(1);
+        let token = self.cancel_token().ok();
+        let mut buf = vec![Complex32::new(0.0, 0.0); max];
+        let mut n = 0;
+
+        while n < max {
+            if token.as_ref().is_some_and(|t| t.is_canceled()) {
+                break;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let end = std::cmp::min(n + mtu, max);
+            match self.read(&mut [&mut buf[n..end]], remaining.as_micros() as i64) {
+                Ok(0) => std::thread::sleep(remaining.min(EMPTY_READ_BACKOFF)),
+                Ok(k) => n += k,
+                Err(Error::Timeout(_)) => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        buf.truncate(n);
+        Ok(buf)
+    }
 }
 
 #[doc(hidden)]
@@ -67,6 +428,57 @@ impl RxStreamer for Box<dyn RxStreamer> {
     fn read(&mut self, buffers: &mut [&mut [Complex32]], timeout_us: i64) -> Result<usize, Error> {
         self.as_mut().read(buffers, timeout_us)
     }
+    fn samples_available(&self) -> Result<usize, Error> {
+        self.as_ref().samples_available()
+    }
+    fn read_with_timeout(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout: Timeout,
+    ) -> Result<usize, Error> {
+        self.as_mut().read_with_timeout(buffers, timeout)
+    }
+    fn sample_rate(&self) -> Result<f64, Error> {
+        self.as_ref().sample_rate()
+    }
+    fn read_raw(
+        &mut self,
+        buf: &mut [u8],
+        format: SampleFormat,
+        timeout_us: i64,
+    ) -> Result<usize, Error> {
+        self.as_mut().read_raw(buf, format, timeout_us)
+    }
+    fn read_with_meta(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<(usize, StreamMeta), Error> {
+        self.as_mut().read_with_meta(buffers, timeout_us)
+    }
+    fn read_exact(
+        &mut self,
+        buffers: &mut [&mut [Complex32]],
+        timeout_us: i64,
+    ) -> Result<(), Error> {
+        self.as_mut().read_exact(buffers, timeout_us)
+    }
+    fn read_to_vec(&mut self, max: usize, timeout_us: i64) -> Result<Vec<Complex32>, Error> {
+        self.as_mut().read_to_vec(max, timeout_us)
+    }
+    fn stats(&self) -> Result<StreamStats, Error> {
+        self.as_ref().stats()
+    }
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        self.as_ref().config()
+    }
+    fn cancel_token(&self) -> Result<CancelToken, Error> {
+        self.as_ref().cancel_token()
+    }
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        self.as_ref().raw_fd()
+    }
 }
 
 /// Transmit samples with a [Device](crate::Device) through one or multiple channels.
@@ -76,6 +488,7 @@ pub trait TxStreamer: Send {
     /// The MTU specifies the maximum payload transfer in a stream operation.
     /// This value can be used as a stream buffer allocation size that can
     /// best optimize throughput given the underlying stream implementation.
+    /// It can be queried right after the streamer is created, before activating it.
     fn mtu(&self) -> Result<usize, Error>;
 
     /// Activate a stream.
@@ -89,6 +502,10 @@ pub trait TxStreamer: Send {
     ///
     /// Call `activate` to enable a stream before using `write()`
     ///
+    /// Drivers that cannot schedule the activation in hardware fall back to waiting on the host
+    /// and log a warning. `None` activates the stream immediately. Activating an active stream is
+    /// a no-op.
+    ///
     /// # Arguments:
     ///   * `time_ns` -- optional activation time in nanoseconds from the time the function is
     ///   called.
@@ -101,7 +518,8 @@ pub trait TxStreamer: Send {
     }
 
     /// Deactivate a stream.
-    /// The implementation will control switches or halt data flow.
+    /// The implementation will control switches or halt data flow. Deactivating an inactive
+    /// stream is a no-op.
     ///
     /// # Arguments:
     ///   * `time_ns` -- optional deactivation time in nanoseconds from the time the function is
@@ -120,6 +538,7 @@ pub trait TxStreamer: Send {
     /// `end_burst` indicates the end of a burst transmission.
     ///
     /// Returns the number of samples written, which may be smaller than the size of the passed arrays.
+    /// Returns [`Error::Timeout`] if no samples could be written within `timeout_us`.
     ///
     /// # Panics
     ///  * If `buffers` are not the same length as the `channels` array passed to [`Device::tx_streamer`](crate::Device::tx_streamer).
@@ -156,6 +575,40 @@ pub trait TxStreamer: Send {
         end_burst: bool,
         timeout_us: i64,
     ) -> Result<(), Error>;
+
+    /// Transmit `samples` as one burst through the first channel of the stream.
+    ///
+    /// The samples are written in [`mtu`](TxStreamer::mtu)-sized chunks with
+    /// [write_all](TxStreamer::write_all). The first chunk is scheduled at `start_ns`, the last
+    /// one ends the burst. Afterwards, the call blocks until the burst is
+    /// [drained](TxStreamer::drain), if the driver supports it.
+    fn send_burst(&mut self, samples: &[Complex32], start_ns: Option<i64>) -> Result<(), Error> {
+        let mtu = self.mtu()?.max(1);
+        let chunks = samples.len().div_ceil(mtu);
+        for (i, chunk) in samples.chunks(mtu).enumerate() {
+            let at_ns = if i == 0 { start_ns } else { None };
+            self.write_all(&[chunk], at_ns, i + 1 == chunks, i64::MAX)?;
+        }
+        match self.drain(i64::MAX) {
+            Err(Error::NotSupported) => Ok(()),
+            r => r,
+        }
+    }
+
+    /// Block until all queued samples have been transmitted.
+    ///
+    /// Call it before [deactivating](TxStreamer::deactivate) the stream to avoid truncating the
+    /// last burst. Returns [`Error::Timeout`] if the queue did not drain within `timeout_us`.
+    fn drain(&mut self, timeout_us: i64) -> Result<(), Error> {
+        let _ = timeout_us;
+        Err(Error::NotSupported)
+    }
+
+    /// Configuration that the streamer was created with. Returns [`Error::NotSupported`] if the
+    /// driver does not track it.
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        Err(Error::NotSupported)
+    }
 }
 
 #[doc(hidden)]
@@ -188,4 +641,68 @@ impl TxStreamer for Box<dyn TxStreamer> {
         self.as_mut()
             .write_all(buffers, at_ns, end_burst, timeout_us)
     }
+    fn send_burst(&mut self, samples: &[Complex32], start_ns: Option<i64>) -> Result<(), Error> {
+        self.as_mut().send_burst(samples, start_ns)
+    }
+    fn drain(&mut self, timeout_us: i64) -> Result<(), Error> {
+        self.as_mut().drain(timeout_us)
+    }
+    fn config(&self) -> Result<StreamerConfig, Error> {
+        self.as_ref().config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        let rate = || Ok(1e6);
+        assert_eq!(Timeout::from(200000).us(1000, rate).unwrap(), 200000);
+        assert_eq!(Timeout::Auto.us(1000, rate).unwrap(), 2000);
+        assert_eq!(Timeout::Infinite.us(1000, rate).unwrap(), i64::MAX);
+        assert!(matches!(
+            Timeout::Auto.us(1000, || Err(Error::NotSupported)),
+            Err(Error::NotSupported)
+        ));
+        assert_eq!(Timeout::from(Duration::from_millis(5)), Timeout::Us(5000));
+    }
+
+    /// Streamer whose reads never return samples.
+    struct Empty {
+        reads: usize,
+    }
+
+    impl RxStreamer for Empty {
+        fn mtu(&self) -> Result<usize, Error> {
+            Ok(1024)
+        }
+        fn activate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+            Ok(())
+        }
+        fn deactivate_at(&mut self, _time_ns: Option<i64>) -> Result<(), Error> {
+            Ok(())
+        }
+        fn read(
+            &mut self,
+            _buffers: &mut [&mut [Complex32]],
+            _timeout_us: i64,
+        ) -> Result<usize, Error> {
+            self.reads += 1;
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn empty_reads() {
+        let mut rx = Empty { reads: 0 };
+        let mut buf = [Complex32::new(0.0, 0.0); 16];
+        assert!(matches!(
+            rx.read_exact(&mut [&mut buf], 20000),
+            Err(Error::Timeout(0))
+        ));
+        assert!(rx.read_to_vec(16, 20000).unwrap().is_empty());
+        assert!(rx.reads <= 2 * 20 + 2, "{} reads", rx.reads);
+    }
 }
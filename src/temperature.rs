@@ -0,0 +1,86 @@
+use std::sync::Weak;
+use std::time::Duration;
+
+use crate::DeviceTrait;
+use crate::GenericDevice;
+
+/// Interval at which the temperature monitor reads the sensor.
+const INTERVAL: Duration = Duration::from_secs(10);
+
+/// Tracks the temperature drift since the last warning.
+struct Drift {
+    threshold: f64,
+    reference: Option<f64>,
+}
+
+impl Drift {
+    fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            reference: None,
+        }
+    }
+
+    /// Returns the drift if it exceeds the threshold, which resets the reference.
+    fn update(&mut self, temperature: f64) -> Option<f64> {
+        let reference = *self.reference.get_or_insert(temperature);
+        let drift = temperature - reference;
+        if drift.abs() > self.threshold {
+            self.reference = Some(temperature);
+            Some(drift)
+        } else {
+            None
+        }
+    }
+}
+
+/// Start the temperature monitor, requested with the `temperature_threshold` argument.
+///
+/// A thread reads the first sensor with `temp` in its name and logs a warning each time the
+/// temperature drifted by more than `threshold` degrees, which suggests that DC and IQ
+/// calibration should be re-run. This is purely advisory. The thread holds a weak reference and
+/// ends once the device is dropped. As long as it runs, [`Arc::get_mut`](std::sync::Arc::get_mut)
+/// and thus [`Device::impl_mut`](crate::Device::impl_mut) fail for the device.
+pub(crate) fn monitor(dev: &GenericDevice, threshold: f64) {
+    let sensor = match dev.sensors() {
+        Ok(s) => s.into_iter().find(|s| s.to_lowercase().contains("temp")),
+        Err(_) => None,
+    };
+    let Some(sensor) = sensor else {
+        log::warn!("temperature monitor: device has no temperature sensor");
+        return;
+    };
+    let dev: Weak<_> = std::sync::Arc::downgrade(dev);
+    std::thread::spawn(move || {
+        let mut drift = Drift::new(threshold);
+        while let Some(dev) = dev.upgrade() {
+            match dev.read_sensor_f64(&sensor) {
+                Ok(t) => {
+                    if let Some(d) = drift.update(t) {
+                        log::warn!(
+                            "temperature monitor: {sensor} drifted by {d:+.1} to {t:.1}, consider re-running DC/IQ calibration"
+                        );
+                    }
+                }
+                Err(e) => log::debug!("temperature monitor: reading {sensor} failed: {e}"),
+            }
+            drop(dev);
+            std::thread::sleep(INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift() {
+        let mut d = Drift::new(5.0);
+        assert_eq!(d.update(40.0), None);
+        assert_eq!(d.update(44.0), None);
+        assert_eq!(d.update(46.0), Some(6.0));
+        assert_eq!(d.update(50.0), None);
+        assert_eq!(d.update(40.0), Some(-6.0));
+    }
+}
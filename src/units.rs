@@ -0,0 +1,146 @@
+//! Newtypes for the units of the API, enabled with the `typed-units` feature.
+//!
+//! The plain `f64` API stays the default. These wrappers make the unit explicit at the call site,
+//! e.g., `dev.set_frequency_hz(Rx, 0, Hz::mhz(927.0))` instead of `927e6`, and convert from and
+//! into `f64` in the base unit.
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Device;
+use crate::DeviceTrait;
+use crate::Direction;
+use crate::Error;
+use crate::RxStreamer;
+use crate::TxStreamer;
+
+macro_rules! unit {
+    ($(#[$m:meta])* $name:ident) => {
+        $(#[$m])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+        pub struct $name(pub f64);
+
+        impl From<f64> for $name {
+            fn from(v: f64) -> Self {
+                Self(v)
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(v: $name) -> Self {
+                v.0
+            }
+        }
+    };
+}
+
+unit!(
+    /// Frequency in Hz.
+    Hz
+);
+unit!(
+    /// Gain in dB.
+    Decibels
+);
+unit!(
+    /// Sample rate in samples per second.
+    SamplesPerSec
+);
+
+impl Hz {
+    /// Frequency from kHz.
+    pub fn khz(v: f64) -> Self {
+        Self(v * 1e3)
+    }
+    /// Frequency from MHz.
+    pub fn mhz(v: f64) -> Self {
+        Self(v * 1e6)
+    }
+    /// Frequency from GHz.
+    pub fn ghz(v: f64) -> Self {
+        Self(v * 1e9)
+    }
+}
+
+impl SamplesPerSec {
+    /// Sample rate from kilosamples per second.
+    pub fn ksps(v: f64) -> Self {
+        Self(v * 1e3)
+    }
+    /// Sample rate from megasamples per second.
+    pub fn msps(v: f64) -> Self {
+        Self(v * 1e6)
+    }
+}
+
+impl<
+        R: RxStreamer + 'static,
+        T: TxStreamer + 'static,
+        D: DeviceTrait<RxStreamer = R, TxStreamer = T> + Clone + 'static,
+    > Device<D>
+{
+    /// Typed version of [`set_frequency`](Self::set_frequency).
+    pub fn set_frequency_hz(
+        &self,
+        direction: Direction,
+        channel: usize,
+        frequency: Hz,
+    ) -> Result<(), Error> {
+        self.set_frequency(direction, channel, frequency.into())
+    }
+    /// Typed version of [`set_sample_rate`](Self::set_sample_rate).
+    pub fn set_sample_rate_sps(
+        &self,
+        direction: Direction,
+        channel: usize,
+        rate: SamplesPerSec,
+    ) -> Result<(), Error> {
+        self.set_sample_rate(direction, channel, rate.into())
+    }
+    /// Typed version of [`set_bandwidth`](Self::set_bandwidth).
+    pub fn set_bandwidth_hz(
+        &self,
+        direction: Direction,
+        channel: usize,
+        bw: Hz,
+    ) -> Result<(), Error> {
+        self.set_bandwidth(direction, channel, bw.into())
+    }
+    /// Typed version of [`set_gain`](Self::set_gain).
+    pub fn set_gain_db(
+        &self,
+        direction: Direction,
+        channel: usize,
+        gain: Decibels,
+    ) -> Result<(), Error> {
+        self.set_gain(direction, channel, gain.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Hz::mhz(927.0), Hz(927e6));
+        assert_eq!(Hz::khz(1.5).0, 1500.0);
+        assert_eq!(f64::from(Hz::ghz(2.4)), 2.4e9);
+        assert_eq!(SamplesPerSec::msps(3.2), SamplesPerSec::from(3.2e6));
+        assert_eq!(SamplesPerSec::ksps(250.0).0, 250e3);
+        let g: f64 = Decibels(30.0).into();
+        assert_eq!(g, 30.0);
+        assert!(Hz::mhz(1.0) < Hz::ghz(1.0));
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn setters() {
+        let dev = Device::from_args("driver=mock").unwrap();
+        dev.set_frequency_hz(Direction::Rx, 0, Hz::mhz(927.0))
+            .unwrap();
+        assert_eq!(dev.frequency(Direction::Rx, 0).unwrap(), 927e6);
+        dev.set_sample_rate_sps(Direction::Rx, 0, SamplesPerSec::msps(3.2))
+            .unwrap();
+        assert_eq!(dev.sample_rate(Direction::Rx, 0).unwrap(), 3.2e6);
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::Args;
+
+/// Default interval at which [`watch`] probes for devices.
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Change in the set of available devices, reported by [`watch`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceEvent {
+    /// A device was plugged in. Contains the [`Args`] returned by [`probe`](crate::probe).
+    Added(Args),
+    /// A device was removed. Contains the [`Args`] with which it was reported before.
+    Removed(Args),
+}
+
+/// Handle to a background thread started by [`watch`].
+///
+/// The thread is stopped when the handle is dropped.
+pub struct Watcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// Stop watching and wait for the background thread to terminate.
+    pub fn stop(self) {
+        // thread is stopped and joined on drop
+        drop(self)
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(h) = self.handle.take() {
+            h.thread().unpark();
+            let _ = h.join();
+        }
+    }
+}
+
+/// Watch for devices being plugged in or removed.
+///
+/// Spawns a thread that calls [`probe`](crate::probe) every [`WATCH_INTERVAL`] and passes a
+/// [`DeviceEvent`] to `callback` for each change. Devices that are already present when the
+/// watcher starts are reported as [`DeviceEvent::Added`]. Use [`watch_with_interval`] to
+/// configure the polling interval.
+pub fn watch(callback: impl Fn(DeviceEvent) + Send + 'static) -> Watcher {
+    watch_with_interval(WATCH_INTERVAL, callback)
+}
+
+/// Watch for devices being plugged in or removed, probing every `interval`.
+///
+/// See [`watch`].
+pub fn watch_with_interval(
+    interval: Duration,
+    callback: impl Fn(DeviceEvent) + Send + 'static,
+) -> Watcher {
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = std::thread::spawn({
+        let stop = stop.clone();
+        move || {
+            let mut devs = Vec::new();
+            while !stop.load(Ordering::SeqCst) {
+                match crate::probe(&Args::new()) {
+                    Ok(cur) => {
+                        for e in diff(&devs, &cur) {
+                            callback(e);
+                        }
+                        devs = cur;
+                    }
+                    Err(e) => log::warn!("watch: probing devices failed: {e}"),
+                }
+                std::thread::park_timeout(interval);
+            }
+        }
+    });
+    Watcher {
+        stop,
+        handle: Some(handle),
+    }
+}
+
+fn diff(prev: &[Args], cur: &[Args]) -> Vec<DeviceEvent> {
+    let removed = prev
+        .iter()
+        .filter(|a| !cur.contains(a))
+        .map(|a| DeviceEvent::Removed(a.clone()));
+    let added = cur
+        .iter()
+        .filter(|a| !prev.contains(a))
+        .map(|a| DeviceEvent::Added(a.clone()));
+    removed.chain(added).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events() {
+        let a: Args = "driver=rtlsdr, index=0".try_into().unwrap();
+        let b: Args = "driver=rtlsdr, index=1".try_into().unwrap();
+        let none: Vec<Args> = Vec::new();
+        let only_a = vec![a.clone()];
+        let only_b = vec![b.clone()];
+        assert_eq!(diff(&none, &only_a), vec![DeviceEvent::Added(a.clone())]);
+        assert_eq!(diff(&only_a, &only_a), vec![]);
+        assert_eq!(
+            diff(&only_a, &only_b),
+            vec![DeviceEvent::Removed(a), DeviceEvent::Added(b)]
+        );
+    }
+}